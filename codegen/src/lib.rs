@@ -55,7 +55,7 @@ impl VisitMut for ParserTransformer {
         let expr = &v.expr;
         let new_expr = quote_spanned!(expr.span() => #expr.map_err(|e| e.into()));
         let method_call: syn::Expr = syn::parse2(new_expr).expect("okay");
-        v.expr = Box::new(method_call);
+        *v.expr = method_call;
     }
 
     fn visit_expr_call_mut(&mut self, call: &mut syn::ExprCall) {
@@ -73,7 +73,7 @@ impl VisitMut for ParserTransformer {
     fn visit_macro_mut(&mut self, m: &mut syn::Macro) {
         if let Some(segment) = m.path.segments.last() {
             let name = segment.ident.to_string();
-            if name == "switch" || name.starts_with("parse_") {
+            if name == "switch" || name == "cut" || name.starts_with("parse_") {
                 let (input, output) = (&self.input, &self.output);
                 let tokens = match syn::parse2::<syn::Expr>(m.tokens.clone()) {
                     Ok(mut expr) => {
@@ -125,6 +125,13 @@ fn wrapping_fn_block(
     let (input, input_ty) = extract_input_ident_ty(function)?;
     let fn_block = &function.block;
 
+    // A memoized result is keyed only by position, which other arguments
+    // could change.
+    if let (Some(_), Some(arg)) = (args.memo, function.sig.inputs.iter().nth(1)) {
+        return Err(arg.span().error("memoized parsers can't take arguments besides the input")
+            .help("the result is memoized by position alone"));
+    }
+
     let span = function.span();
     let mark_ident = parse_marker_ident(input.span());
     let info_ident = parser_info_ident(function.sig.ident.span());
@@ -147,35 +154,89 @@ fn wrapping_fn_block(
     };
 
     let (rewind, peek) = (args.rewind.map(rewind_expr), args.peek.map(rewind_expr));
-    let new_block_tokens = {
-        let (name, raw) = (&function.sig.ident, args.raw.is_some());
-        let name_str = name.to_string();
-        quote_spanned!(span => {
-            let ___info = #scope::input::ParserInfo { name: #name_str, raw: #raw };
+    let commit = args.commit.map(|span| quote_spanned! { span =>
+        #scope::input::Pear::cut(#input);
+    });
+
+    let progress = match args.label.is_some() || args.memo.is_some() {
+        true => quote!(let ___progress = #scope::input::Input::progress(#input);),
+        false => quote!(),
+    };
+
+    // A labeled parser that fails without consuming input reports its label.
+    let (label, known, relabel) = match &args.label {
+        Some(label) => (
+            quote!(.with_label(#label)),
+            quote!(let ___known = #scope::input::Pear::expecting(#input, ___progress);),
+            quote!(if let Err(ref mut ___e) = ___res {
+                #scope::input::Pear::relabel(#input, &___info, ___progress, ___known, ___e);
+            }),
+        ),
+        None => (quote!(), quote!(), quote!()),
+    };
+
+    let name_str = function.sig.ident.to_string();
+
+    let run = quote_spanned!(span => {
+        if let Some(ref mut ___debugger) = #input.options.debugger {
+            ___debugger.on_entry(&___info);
+        }
+
+        #known
+        let mut ___mark = #scope::input::Input::mark(#input, &___info);
+        let mut ___res: #ret_ty = #result_map(&___info, &mut ___mark);
+        #relabel
+        match ___res {
+            Ok(_) => { #peek #commit },
+            Err(ref mut ___e) if #input.options.stacked_context => {
+                let ___ctxt = #scope::input::Input::context(#input, ___mark);
+                ___e.push_info(___info, ___ctxt);
+                #rewind
+            },
+            Err(_) => { #rewind },
+        }
+
+        if #input.options.debugger.is_some() {
+            let ___ctxt = #scope::input::Input::context(#input, ___mark);
             if let Some(ref mut ___debugger) = #input.options.debugger {
-                ___debugger.on_entry(&___info);
+                ___debugger.on_exit(&___info, ___res.is_ok(), ___ctxt);
             }
+        }
 
-            let mut ___mark = #scope::input::Input::mark(#input, &___info);
-            let mut ___res: #ret_ty = #result_map(&___info, &mut ___mark);
-            match ___res {
-                Ok(_) => { #peek },
-                Err(ref mut ___e) if #input.options.stacked_context => {
-                    let ___ctxt = #scope::input::Input::context(#input, ___mark);
-                    ___e.push_info(___info, ___ctxt);
-                    #rewind
-                },
-                Err(_) => { #rewind },
-            }
+        #scope::input::Input::unmark(#input, ___mark);
+        ___res
+    });
 
-            if #input.options.debugger.is_some() {
-                let ___ctxt = #scope::input::Input::context(#input, ___mark);
-                if let Some(ref mut ___debugger) = #input.options.debugger {
-                    ___debugger.on_exit(&___info, ___res.is_ok(), ___ctxt);
+    // A memoized parser replays its result at a position it has parsed from,
+    // and is run again while a left-recursive call's seed keeps growing.
+    let run = match args.memo {
+        Some(span) => {
+            quote_spanned!(span => {
+                let ___memo_name = concat!(module_path!(), "::", #name_str);
+                let ___res = #scope::input::Pear::recall(#input, &___info, ___memo_name, ___progress);
+
+                if let Some(___res) = ___res {
+                    return ___res;
                 }
-            }
 
-            ___res
+                #scope::input::Pear::enter_memo(#input, &___info, ___memo_name, ___progress);
+                loop {
+                    let ___res: #ret_ty = #run;
+                    if let Some(___res) = #scope::input::Pear::exit_memo(#input, ___res) {
+                        break ___res;
+                    }
+                }
+            })
+        }
+        None => run,
+    };
+
+    let new_block_tokens = {
+        let raw = args.raw.is_some();
+        quote_spanned!(span => {
+            let ___info = #scope::input::ParserInfo::new(#name_str, #raw)#label;
+            #progress
+            #run
         })
     };
 
@@ -204,7 +265,7 @@ fn parser_attribute(input: proc_macro::TokenStream, args: &AttrArgs) -> PResult<
 
     let scope = args.raw.map(|_| quote!(crate)).unwrap_or_else(|| quote!(pear));
     let inline = syn::Attribute::parse_outer.parse2(quote!(#[inline])).unwrap();
-    function.block = Box::new(wrapping_fn_block(&function, scope, args, &ret_ty)?);
+    *function.block = wrapping_fn_block(&function, scope, args, &ret_ty)?;
     function.attrs.extend(inline);
 
     Ok(quote! {
@@ -250,10 +311,15 @@ impl Case {
                     let mut call_expr = call.expr.clone();
                     call_expr.args.insert(0, input.clone());
                     let call_expr = quote!({
-                        let ___preserve_error = #input.emit_error;
-                        #input.emit_error = false;
+                        let ___alternative = #input.enter_alternative();
                         let ___call_result = #call_expr;
-                        #input.emit_error = ___preserve_error;
+                        let ___failed = ___call_result.is_err();
+                        if #input.exit_alternative(___alternative, ___failed) {
+                            if let Err(___e) = ___call_result {
+                                return Err(___e.into());
+                            }
+                        }
+
                         ___call_result
                     });
 
@@ -338,6 +404,27 @@ impl Switch {
 /// - The [`Input::unmark()`] method is called after the function executes,
 ///   passing in the current mark.
 ///
+/// The attribute accepts the following comma-separated arguments:
+///
+/// - `rewind`: on failure, the input is rewound to where the function began.
+/// - `peek`: on success, the input is rewound to where the function began.
+/// - `commit`: on success, the enclosing alternative is committed to, as if
+///   by `cut!()`.
+/// - `memo`: the result, or error, is memoized by position in a table owned
+///   by the `Pear` and replayed when the function is called again at the
+///   same position. The output must be `Clone + 'static`, the error type
+///   must be the `Pear`'s diagnostic type, the input must implement `Rewind`
+///   and `Input::progress()`, and the result must depend only on the
+///   position: the function can't take arguments besides the input. See
+///   `Pear::memo_stats()`. A memoized function may
+///   be left-recursive, directly or through other functions: the recursive
+///   call at first fails, then the function is run again, with the call
+///   replaying the previous result, for as long as the result grows longer.
+/// - `label = "..."`: a human-readable name for what the function parses,
+///   reported as what was expected when the function fails without
+///   consuming input on a mismatched token, slice, or EOF. Errors the
+///   function raises itself are left as they are.
+///
 /// # Example
 ///
 /// ```rust
@@ -385,7 +472,8 @@ pub fn parser(
 
 /// Invoked much like match, except each condition must be a parser, which is
 /// executed, and the corresponding arm is executed only if the parser succeeds.
-/// Once a condition succeeds, no other condition is executed.
+/// Once a condition succeeds, no other condition is executed. If a condition
+/// fails after a `cut!()`, its error is returned instead of trying the next.
 ///
 /// ```rust,ignore
 /// switch! {
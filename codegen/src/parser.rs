@@ -79,7 +79,7 @@ type CallPatterns = Punctuated<CallPattern, Token![|]>;
 
 #[derive(Debug)]
 pub enum Pattern {
-    Wild(Token![_]),
+    Wild(#[allow(dead_code)] Token![_]),
     Calls(CallPatterns),
 }
 
@@ -203,8 +203,10 @@ impl Parse for Case {
 
 #[derive(Debug)]
 pub struct Context {
+    #[allow(dead_code)]
     pub info: syn::Ident,
     pub input: syn::Expr,
+    #[allow(dead_code)]
     pub marker: syn::Expr,
     pub output: syn::Type,
 }
@@ -255,26 +257,50 @@ pub struct AttrArgs {
     pub raw: Option<Span>,
     pub rewind: Option<Span>,
     pub peek: Option<Span>,
+    pub commit: Option<Span>,
+    pub memo: Option<Span>,
+    pub label: Option<syn::LitStr>,
 }
 
 impl Parse for AttrArgs {
     fn parse(input: SynParseStream) -> PResult<Self> {
-        let args = input.call(<Punctuated<syn::Ident, Token![,]>>::parse_terminated)?;
-        let (mut raw, mut rewind, mut peek) = Default::default();
-        for case in args.iter() {
-            if case == "raw" {
-                raw = Some(case.span());
-            } else if case == "rewind" {
-                rewind = Some(case.span());
-            } else if case == "peek" {
-                peek = Some(case.span());
+        let args = input.call(<Punctuated<syn::Meta, Token![,]>>::parse_terminated)?;
+        let (mut raw, mut rewind, mut peek, mut commit, mut memo, mut label) = Default::default();
+        for arg in args.iter() {
+            let path = arg.path();
+            if path.is_ident("label") {
+                label = match arg {
+                    syn::Meta::NameValue(syn::MetaNameValue {
+                        value: syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }), ..
+                    }) => Some(s.clone()),
+                    _ => return Err(arg.span().error("expected `label = \"...\"`")),
+                };
+
+                continue;
+            }
+
+            if !matches!(arg, syn::Meta::Path(_)) {
+                return Err(arg.span().error("unexpected attribute argument value"));
+            }
+
+            if path.is_ident("raw") {
+                raw = Some(path.span());
+            } else if path.is_ident("rewind") {
+                rewind = Some(path.span());
+            } else if path.is_ident("peek") {
+                peek = Some(path.span());
+            } else if path.is_ident("commit") {
+                commit = Some(path.span());
+            } else if path.is_ident("memo") {
+                memo = Some(path.span());
             } else {
-                return Err(case.span()
-                           .error(format!("unknown attribute argument `{}`", case))
-                           .help("supported arguments are: `rewind`, `peek`"));
+                let name = quote::quote!(#path).to_string();
+                return Err(path.span()
+                           .error(format!("unknown attribute argument `{}`", name))
+                           .help("supported arguments are: `rewind`, `peek`, `commit`, `memo`, `label = \"...\"`"));
             }
         }
 
-        Ok(AttrArgs { raw, rewind, peek })
+        Ok(AttrArgs { raw, rewind, peek, commit, memo, label })
     }
 }
@@ -0,0 +1,98 @@
+#![warn(rust_2018_idioms)]
+
+use pear::input::{Text, Pear, Result};
+use pear::macros::{parser, parse, switch};
+use pear::parsers::*;
+
+#[derive(Debug, Clone)]
+enum Op {
+    Add, Sub, Mul, Div
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Binary(Op, Box<Expr>, Box<Expr>),
+    Int(isize)
+}
+
+impl Expr {
+    fn eval(&self) -> isize {
+        match *self {
+            Expr::Binary(Op::Add, ref e1, ref e2) => e1.eval() + e2.eval(),
+            Expr::Binary(Op::Sub, ref e1, ref e2) => e1.eval() - e2.eval(),
+            Expr::Binary(Op::Mul, ref e1, ref e2) => e1.eval() * e2.eval(),
+            Expr::Binary(Op::Div, ref e1, ref e2) => e1.eval() / e2.eval(),
+            Expr::Int(val) => val
+        }
+    }
+}
+
+type Input<'a> = Text<'a>;
+
+#[parser]
+fn int<'a>(input: &mut Pear<Input<'a>>) -> Result<Expr, Input<'a>> {
+    let sign = switch! { eat('-') => -1, _ => 1 };
+    let num = take_some_while(|c| c.is_ascii_digit())?;
+    Expr::Int(sign * num.parse::<isize>().unwrap())
+}
+
+#[parser]
+fn op<'a>(input: &mut Pear<Input<'a>>, ops: &[(char, Op)]) -> Result<Op, Input<'a>> {
+    skip_while(|c| c.is_whitespace())?;
+    let c = eat_if(|c| ops.iter().any(|(o, _)| o == c))?;
+    skip_while(|c| c.is_whitespace())?;
+    ops.iter().find(|(o, _)| *o == c).map(|(_, op)| op.clone()).unwrap()
+}
+
+// val := '(' expr ')' | int
+#[parser]
+fn val<'a>(input: &mut Pear<Input<'a>>) -> Result<Expr, Input<'a>> {
+    switch! {
+        eat('(') => (expr()?, eat(')')?).0,
+        _ => int()?
+    }
+}
+
+// term := term ('*' | '/') val | val
+#[parser(rewind)]
+fn product<'a>(input: &mut Pear<Input<'a>>) -> Result<Expr, Input<'a>> {
+    let left = term()?;
+    let op = op(&[('*', Op::Mul), ('/', Op::Div)])?;
+    Expr::Binary(op, Box::new(left), Box::new(val()?))
+}
+
+#[parser(memo)]
+fn term<'a>(input: &mut Pear<Input<'a>>) -> Result<Expr, Input<'a>> {
+    switch! {
+        e@product() => e,
+        _ => val()?
+    }
+}
+
+// expr := expr ('+' | '-') term | term
+#[parser(rewind)]
+fn sum<'a>(input: &mut Pear<Input<'a>>) -> Result<Expr, Input<'a>> {
+    let left = expr()?;
+    let op = op(&[('+', Op::Add), ('-', Op::Sub)])?;
+    Expr::Binary(op, Box::new(left), Box::new(term()?))
+}
+
+#[parser(memo)]
+fn expr<'a>(input: &mut Pear<Input<'a>>) -> Result<Expr, Input<'a>> {
+    switch! {
+        e@sum() => e,
+        _ => term()?
+    }
+}
+
+fn eval_expr(string: &str) -> Option<isize> {
+    parse!(expr: Text::from(string)).map(|e| e.eval()).ok()
+}
+
+fn main() {
+    println!("Result: {:?}", eval_expr("(4 * (3 + 2)) * 2"));
+    println!("Result: {:?}", eval_expr("-4 + -2 - 3"));
+    println!("Result: {:?}", eval_expr("10 - 4 - 3"));
+    println!("Result: {:?}", eval_expr("64 / 4 / 2"));
+    println!("Result: {:?}", eval_expr("-1"));
+}
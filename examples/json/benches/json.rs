@@ -22,6 +22,7 @@ fn parse_json<'a, I: Input<'a>>(input: I) -> Result<JsonValue<'a>, I> {
 // if it parses. As a result, our parser will be much slower. You can immitate
 // the PEST parser's behavior by changing the parser so that it doesn't build
 // real values and instead returns dummy values.
+#[allow(clippy::result_large_err)]
 pub fn simple_data(c: &mut Criterion) {
     let data = include_str!("../assets/simple.json");
     c.bench_function("simple", |b| b.iter(|| black_box(parse_json(data))));
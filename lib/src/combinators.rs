@@ -1,4 +1,6 @@
-use crate::input::{Pear, Input, Rewind, Token, Result};
+use crate::error::{Expected, ParseError};
+use crate::input::{self, Pear, Input, Rewind, Seek, Token, Trivia, Skip, Limited};
+use crate::result::Result;
 use crate::macros::parser;
 use crate::parsers::*;
 
@@ -12,29 +14,302 @@ pub trait Collection<A>: Default + Extend<A> {
 impl<A, T: Default + Extend<A>> Collection<A> for T {  }
 
 /// Parses `p`, returning `Some` if it succeeds and `None` if it fails. Discards
-/// the error message.
-pub fn ok<I, P, O>(input: &mut Pear<I>, p: P) -> Option<O>
-    where I: Input, P: FnOnce(&mut Pear<I>) -> Result<O, I>
+/// the error message. If `p` fails after a [cut](Pear::cut()), the cut is left
+/// in place, committing the enclosing alternative, but the error is lost:
+/// callers that must propagate it should check [`Pear::is_cut()`].
+pub fn ok<I, P, O, E, D>(input: &mut Pear<I, D>, p: P) -> Option<O>
+    where I: Input, P: FnOnce(&mut Pear<I, D>) -> Result<O, I::Context, E>
 {
-    let save = input.emit_error;
-    input.emit_error = false;
-    let ok = p(input).ok();
-    input.emit_error = save;
-    ok
+    try_ok(input, p).ok().flatten()
+}
+
+/// Like [`ok()`], but returns `p`'s error if `p` fails after a cut.
+fn try_ok<I, P, O, E, D>(input: &mut Pear<I, D>, p: P) -> Result<Option<O>, I::Context, E>
+    where I: Input, P: FnOnce(&mut Pear<I, D>) -> Result<O, I::Context, E>
+{
+    let saved = input.enter_alternative();
+    let result = p(input);
+    match input.exit_alternative(saved, result.is_err()) {
+        true => result.map(Some),
+        false => Ok(result.ok()),
+    }
 }
 
 /// Parses `p`, returning `true` if it succeeds and `false` if it fails.
-/// Discards the error message.
-pub fn succeeds<I, P, O>(input: &mut Pear<I>, p: P) -> bool
-    where I: Input, P: FnOnce(&mut Pear<I>) -> Result<O, I>
+/// Discards the error message. If `p` fails after a [cut](Pear::cut()), the
+/// cut is left in place, committing the enclosing alternative, but the error
+/// is lost, as with [`ok()`].
+pub fn succeeds<I, P, O, E, D>(input: &mut Pear<I, D>, p: P) -> bool
+    where I: Input, P: FnOnce(&mut Pear<I, D>) -> Result<O, I::Context, E>
 {
     ok(input, p).is_some()
 }
 
+/// Parses `p` with the [`Trivia`] input in raw mode, so that no trivia is
+/// skipped, restoring the previous mode afterwards.
+pub fn raw<I, S, P, O, E, D>(input: &mut Pear<Trivia<I, S>, D>, p: P) -> Result<O, I::Context, E>
+    where I: Input, S: Skip<I>, P: FnOnce(&mut Pear<Trivia<I, S>, D>) -> Result<O, I::Context, E>
+{
+    let save = input.set_raw(true);
+    let result = p(input);
+    input.set_raw(save);
+    result
+}
+
+/// Runs `p`, returning its result and whether it failed because the input was
+/// cut short or ended at malformed data, in which case it can't be recovered
+/// from.
+fn attempt<I, P, O, E, D>(input: &mut Pear<I, D>, p: P) -> (Result<O, I::Context, E>, bool)
+    where I: Input, P: FnOnce(&mut Pear<I, D>) -> Result<O, I::Context, E>
+{
+    let saved = std::mem::replace(&mut input.exhausted, false);
+    let result = p(input);
+    let exhausted = std::mem::replace(&mut input.exhausted, saved) && result.is_err();
+    input.exhausted |= exhausted;
+    (result, exhausted)
+}
+
+/// Parses `p`, recovering if it fails: the error is recorded as a diagnostic
+/// on the [`Pear`], tokens are skipped up to but excluding the first for which
+/// `sync` returns `true`, or to EOF, and `O::default()` is returned in place of
+/// `p`'s output. Diagnostics can be retrieved with [`Pear::diagnostics()`] or
+/// via `parse!(recover ...)`.
+///
+/// Errors aren't recovered from, and are returned as is, while errors are
+/// being discarded, as in a `switch!` case, or if they're due to an I/O error,
+/// malformed data, or incomplete input. Since the synchronizing token isn't
+/// consumed, the caller must consume it to make progress. Diagnostics are of
+/// the `Pear`'s diagnostic type, so `p`'s error type `E` must be that type:
+/// see [`Pear::with_error_type()`].
+///
+/// # Example
+///
+/// ```rust
+/// use pear::input::{Pear, Text};
+/// use pear::combinators::recover;
+/// use pear::parsers::*;
+///
+/// let mut input = Pear::from(Text::from("1;x;3"));
+/// let mut numbers = vec![];
+/// loop {
+///     let digit = recover(&mut input, |i| eat_if(i, char::is_ascii_digit), |&c| c == ';');
+///     numbers.push(digit.unwrap());
+///     if eat(&mut input, ';').is_err() {
+///         break;
+///     }
+/// }
+///
+/// assert_eq!(numbers, ['1', '\0', '3']);
+/// assert_eq!(input.diagnostics().len(), 1);
+/// ```
+pub fn recover<I, P, O, F, E>(input: &mut Pear<I, E>, p: P, mut sync: F) -> Result<O, I::Context, E>
+    where I: Input,
+          O: Default,
+          P: FnOnce(&mut Pear<I, E>) -> Result<O, I::Context, E>,
+          F: FnMut(&I::Token) -> bool
+{
+    match attempt(input, p) {
+        (Err(e), false) if input.emit_error => {
+            input.push_diagnostic(e);
+            input.skip(|t| !sync(t));
+            Ok(O::default())
+        }
+        (result, _) => result,
+    }
+}
+
+/// Like [`recover()`] but synchronizes on a parser: tokens are skipped until
+/// `sync` succeeds, consuming what it parsed, or to EOF. `sync` must not
+/// consume input when it fails.
+///
+/// # Example
+///
+/// ```rust
+/// use pear::input::{Pear, Text};
+/// use pear::combinators::recover_until;
+/// use pear::parsers::*;
+///
+/// let mut input = Pear::from(Text::from("a:=1 b==2 c:=3"));
+/// let mut names = vec![];
+/// while eof(&mut input).is_err() {
+///     skip_while(&mut input, |&c| c == ' ').unwrap();
+///     let name = recover_until(&mut input, |i| {
+///         let name = eat_any(i)?;
+///         eat_slice(i, ":=")?;
+///         eat_any(i)?;
+///         Ok(name)
+///     }, |i| eat(i, ' '));
+///
+///     names.push(name.unwrap());
+/// }
+///
+/// assert_eq!(names, ['a', '\0', 'c']);
+/// assert_eq!(input.diagnostics().len(), 1);
+/// ```
+pub fn recover_until<I, P, O, S, T, E, F>(
+    input: &mut Pear<I, E>,
+    p: P,
+    mut sync: S
+) -> Result<O, I::Context, E>
+    where I: Input,
+          O: Default,
+          P: FnOnce(&mut Pear<I, E>) -> Result<O, I::Context, E>,
+          S: FnMut(&mut Pear<I, E>) -> Result<T, I::Context, F>
+{
+    match attempt(input, p) {
+        (Err(e), false) if input.emit_error => {
+            input.push_diagnostic(e);
+            while !succeeds(input, &mut sync) && input.eat(|_| true).is_some() {  }
+            Ok(O::default())
+        }
+        (result, _) => result,
+    }
+}
+
+/// Finishes a `parse!(recover ...)`: checks for EOF after a successful parse
+/// and returns the output, if any, with all diagnostics.
+#[doc(hidden)]
+pub fn recovered<I, O, E>(
+    input: &mut Pear<I, E>,
+    result: Result<O, I::Context, E>
+) -> (Option<O>, Vec<ParseError<I::Context, E>>)
+    where I: Input, E: From<input::Expected<I>>
+{
+    let output = match result {
+        Ok(output) => {
+            if let Err(e) = eof(input) {
+                input.push_diagnostic(e.into());
+            }
+
+            Some(output)
+        }
+        Err(e) => {
+            input.push_diagnostic(e);
+            None
+        }
+    };
+
+    (output, input.take_diagnostics())
+}
+
+/// Parses `p` at the absolute position `offset`, such as one read from an
+/// offset table, then returns to the current position whether or not `p`
+/// succeeded. Contexts in errors from `p` point at the position `p` failed at.
+/// Fails without running `p` if `offset` isn't a valid position.
+///
+/// # Example
+///
+/// ```rust
+/// use pear::input::{Pear, Cursor};
+/// use pear::combinators::at;
+/// use pear::parsers::*;
+///
+/// // A one-byte offset to a two-byte string, then the string itself.
+/// let mut input = Pear::from(Cursor::from(&b"\x03!!hi"[..]));
+/// let offset = eat_any(&mut input).unwrap() as usize;
+/// let string = at(&mut input, offset, |i| take_n(i, 2)).unwrap();
+/// assert_eq!(string.values, b"hi");
+/// assert_eq!(eat_any(&mut input).unwrap(), b'!');
+/// ```
+#[parser(raw)]
+pub fn at<I, P, O, E, D>(input: &mut Pear<I, D>, offset: usize, p: P) -> Result<O, I::Context, E>
+    where I: Seek,
+          P: FnOnce(&mut Pear<I, D>) -> Result<O, I::Context, E>,
+          E: From<input::Expected<I>>
+{
+    let back = parse_current_marker!();
+    if !input.seek_to(offset) {
+        input.unmark(back);
+        let error = crate::iformat!("offset {} is out of bounds", offset);
+        return parse_error!(input::Expected::<I>::from(error).into());
+    }
+
+    let result = p(input);
+    input.rewind_to(back);
+    input.unmark(back);
+    result
+}
+
+/// Parses `p` against a [`Limited`] window of the next `n` units of the input,
+/// such as a length-prefixed record. Fails unless `p` consumes the entire
+/// window. Positions in errors, and in contexts, are relative to the entire
+/// input, not to the window.
+///
+/// # Example
+///
+/// ```rust
+/// use pear::input::{Pear, Bytes};
+/// use pear::combinators::limit;
+/// use pear::parsers::*;
+///
+/// let mut input: Pear<Bytes> = Pear::new(b"\x02abc");
+/// let n = eat_any(&mut input).unwrap() as usize;
+/// let record = limit(&mut input, n, |i| take_while(i, |_| true)).unwrap();
+/// assert_eq!(record, b"ab");
+/// assert_eq!(eat_any(&mut input).unwrap(), b'c');
+/// ```
+#[parser(raw)]
+pub fn limit<I, P, O, E, D>(input: &mut Pear<I, D>, n: usize, p: P) -> Result<O, I::Context, E>
+    where I: Input<Marker = usize> + Rewind,
+          P: for<'a> FnOnce(&mut Pear<Limited<'a, I>, D>) -> Result<O, I::Context, E>,
+          E: From<input::Expected<I>>
+{
+    let (output, remaining) = limited(n, p)?;
+    if remaining > 0 {
+        let expected = match input.has(remaining) {
+            true => Expected::Eof(input.token()),
+            false => {
+                let needed = missing(input, remaining);
+                exhausted(input, needed, Expected::slice(None::<&I::Slice>, None))
+            }
+        };
+
+        return parse_error!(expected.into());
+    }
+
+    Ok(output)
+}
+
+/// Like [`limit()`], but skips whatever part of the window `p` doesn't
+/// consume instead of failing.
+#[parser(raw)]
+pub fn limit_skip<I, P, O, E, D>(input: &mut Pear<I, D>, n: usize, p: P) -> Result<O, I::Context, E>
+    where I: Input<Marker = usize> + Rewind,
+          P: for<'a> FnOnce(&mut Pear<Limited<'a, I>, D>) -> Result<O, I::Context, E>,
+          E: From<input::Expected<I>>
+{
+    let (output, remaining) = limited(n, p)?;
+    if remaining > 0 && input.eat_slice(remaining, |_| true).is_none() {
+        let needed = missing(input, remaining);
+        let expected = exhausted(input, needed, Expected::slice(None::<&I::Slice>, None));
+        return parse_error!(expected.into());
+    }
+
+    Ok(output)
+}
+
+/// Runs `p` against a window of the next `n` units of `input`. Returns `p`'s
+/// output and the number of units of the window it left unconsumed. Failures
+/// and diagnostics recorded in the window are recorded in `input`.
+fn limited<I, P, O, E, D>(
+    input: &mut Pear<I, D>,
+    n: usize,
+    p: P,
+) -> Result<(O, usize), I::Context, E>
+    where I: Input<Marker = usize> + Rewind,
+          P: for<'a> FnOnce(&mut Pear<Limited<'a, I>, D>) -> Result<O, I::Context, E>,
+          E: From<input::Expected<I>>
+{
+    input.limited(n, |window| {
+        let output = p(window)?;
+        Ok((output, window.remaining()))
+    })
+}
+
 /// Parses `p` until `p` fails, returning the last successful `p`.
 #[parser(raw)]
-pub fn last_of_many<I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<O, I>
-    where I: Input, P: FnMut(&mut Pear<I>) -> Result<O, I>
+pub fn last_of_many<I, O, P, E, D>(input: &mut Pear<I, D>, mut p: P) -> Result<O, I::Context, E>
+    where I: Input, P: FnMut(&mut Pear<I, D>) -> Result<O, I::Context, E>
 {
     loop {
         let output = p()?;
@@ -46,10 +321,15 @@ pub fn last_of_many<I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<O, I>
 
 /// Skips all tokens that match `f` before and after a `p`, returning `p`.
 #[parser(raw)]
-pub fn surrounded<I, O, F, P>(input: &mut Pear<I>, mut p: P, mut f: F) -> Result<O, I>
+pub fn surrounded<I, O, F, P, E, D>(
+    input: &mut Pear<I, D>,
+    mut p: P,
+    mut f: F,
+) -> Result<O, I::Context, E>
     where I: Input,
           F: FnMut(&I::Token) -> bool,
-          P: FnMut(&mut Pear<I>) -> Result<O, I>
+          P: FnMut(&mut Pear<I, D>) -> Result<O, I::Context, E>,
+          E: From<input::Expected<I>>
 {
     skip_while(&mut f)?;
     let output = p()?;
@@ -60,8 +340,8 @@ pub fn surrounded<I, O, F, P>(input: &mut Pear<I>, mut p: P, mut f: F) -> Result
 /// Parses as many `p` as possible until EOF is reached, collecting them into a
 /// `C`. Fails if `p` every fails. `C` may be empty.
 #[parser(raw)]
-pub fn collect<C, I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<C, I>
-    where C: Collection<O>, I: Input, P: FnMut(&mut Pear<I>) -> Result<O, I>
+pub fn collect<C, I, O, P, E, D>(input: &mut Pear<I, D>, mut p: P) -> Result<C, I::Context, E>
+    where C: Collection<O>, I: Input, P: FnMut(&mut Pear<I, D>) -> Result<O, I::Context, E>
 {
     let mut collection = C::default();
     loop {
@@ -76,8 +356,8 @@ pub fn collect<C, I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<C, I>
 /// Parses as many `p` as possible until EOF is reached, collecting them into a
 /// `C`. Fails if `p` ever fails. `C` is not allowed to be empty.
 #[parser(raw)]
-pub fn collect_some<C, I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<C, I>
-    where C: Collection<O>, I: Input, P: FnMut(&mut Pear<I>) -> Result<O, I>
+pub fn collect_some<C, I, O, P, E, D>(input: &mut Pear<I, D>, mut p: P) -> Result<C, I::Context, E>
+    where C: Collection<O>, I: Input, P: FnMut(&mut Pear<I, D>) -> Result<O, I::Context, E>
 {
     let mut collection = C::default();
     loop {
@@ -89,10 +369,13 @@ pub fn collect_some<C, I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<C, I>
 }
 
 /// Parses as many `p` as possible until EOF is reached or `p` fails, collecting
-/// them into a `C`. `C` may be empty.
+/// them into a `C`. `C` may be empty. If `p` fails after a [cut](Pear::cut()),
+/// its error is returned.
 #[parser(raw)]
-pub fn try_collect<C, I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<C, I>
-    where C: Collection<O>, I: Input + Rewind, P: FnMut(&mut Pear<I>) -> Result<O, I>
+pub fn try_collect<C, I, O, P, E, D>(input: &mut Pear<I, D>, mut p: P) -> Result<C, I::Context, E>
+    where C: Collection<O>,
+          I: Input + Rewind,
+          P: FnMut(&mut Pear<I, D>) -> Result<O, I::Context, E>
 {
     let mut collection = C::default();
     loop {
@@ -101,17 +384,23 @@ pub fn try_collect<C, I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<C, I>
         }
 
         // FIXME: We should be able to call `parse_marker!` here.
-        let start = input.mark(&crate::input::ParserInfo {
-            name: "try_collect",
-            raw: true
-        });
-
-        match ok(input, |i| p(i)) {
-            Some(val) => collection.push(val),
-            None => {
-                input.rewind_to(start);
-                break;
-            }
+        let start = input.mark(&crate::input::ParserInfo::new("try_collect", true));
+
+        let saved = input.enter_alternative();
+        let parsed = p(input);
+        if input.exit_alternative(saved, parsed.is_err()) {
+            input.unmark(start);
+            return parsed.map(|_| collection);
+        }
+
+        if parsed.is_err() {
+            input.rewind_to(start);
+        }
+
+        input.unmark(start);
+        match parsed {
+            Ok(val) => collection.push(val),
+            Err(_) => break,
         }
     }
 
@@ -123,18 +412,19 @@ pub fn try_collect<C, I, O, P>(input: &mut Pear<I>, mut p: P) -> Result<C, I>
 ///
 /// START (item SEPERATOR)* END
 #[parser(raw)]
-pub fn delimited_collect<C, I, T, S, O, P>(
-    input: &mut Pear<I>,
+pub fn delimited_collect<C, I, T, S, O, P, E, D>(
+    input: &mut Pear<I, D>,
     start: T,
     mut item: P,
     separator: S,
     end: T,
-) -> Result<C, I>
+) -> Result<C, I::Context, E>
     where C: Collection<O>,
           I: Input,
           T: Token<I> + Clone,
           S: Into<Option<T>>,
-          P: FnMut(&mut Pear<I>) -> Result<O, I>,
+          P: FnMut(&mut Pear<I, D>) -> Result<O, I::Context, E>,
+          E: From<input::Expected<I>>,
 {
     eat(start)?;
 
@@ -162,15 +452,15 @@ pub fn delimited_collect<C, I, T, S, O, P>(
 ///
 /// item (SEPERATOR item)*
 #[parser(raw)]
-pub fn series<C, I, S, O, P>(
-    input: &mut Pear<I>,
+pub fn series<C, I, S, O, P, E, D>(
+    input: &mut Pear<I, D>,
     mut item: P,
     seperator: S,
-) -> Result<C, I>
+) -> Result<C, I::Context, E>
     where C: Collection<O>,
           I: Input,
           S: Token<I> + Clone,
-          P: FnMut(&mut Pear<I>) -> Result<O, I>,
+          P: FnMut(&mut Pear<I, D>) -> Result<O, I::Context, E>,
 {
     let mut collection = C::default();
     loop {
@@ -187,25 +477,27 @@ pub fn series<C, I, S, O, P>(
 /// Gramatically, this is:
 ///
 /// item (SEPERATOR item)* SEPERATOR?
+///
+/// If an item after a separator fails after a [cut](Pear::cut()), its error is
+/// returned instead of ending the series.
 #[parser(raw)]
-pub fn trailing_series<C, I, S, O, P>(
-    input: &mut Pear<I>,
+pub fn trailing_series<C, I, S, O, P, E, D>(
+    input: &mut Pear<I, D>,
     mut item: P,
     seperator: S,
-) -> Result<C, I>
+) -> Result<C, I::Context, E>
     where C: Collection<O>,
           I: Input,
           S: Token<I> + Clone,
-          P: FnMut(&mut Pear<I>) -> Result<O, I>,
+          P: FnMut(&mut Pear<I, D>) -> Result<O, I::Context, E>,
 {
     let mut collection = C::default();
     let mut have_some = false;
     loop {
         if have_some {
-            if let Some(item) = ok(input, |i| item(i)) {
-                collection.push(item);
-            } else {
-                break
+            match try_ok(|i| item(i))? {
+                Some(item) => collection.push(item),
+                None => break,
             }
         } else {
             collection.push(item()?);
@@ -225,16 +517,16 @@ pub fn trailing_series<C, I, S, O, P>(
 ///
 /// PREFIX (item SEPERATOR)*
 #[parser(raw)]
-pub fn prefixed_series<C, I, T, O, P>(
-    input: &mut Pear<I>,
+pub fn prefixed_series<C, I, T, O, P, E, D>(
+    input: &mut Pear<I, D>,
     prefix: T,
     item: P,
     seperator: T,
-) -> Result<C, I>
+) -> Result<C, I::Context, E>
     where C: Collection<O>,
           I: Input,
           T: Token<I> + Clone,
-          P: FnMut(&mut Pear<I>) -> Result<O, I>,
+          P: FnMut(&mut Pear<I, D>) -> Result<O, I::Context, E>,
 {
     if !succeeds(input, |i| eat(i, prefix)) {
         return Ok(C::default());
@@ -101,10 +101,10 @@ impl Tree<Info> {
         };
 
         #[cfg(feature = "color")]
-        println!("{}{} ({})", info.parser.name.paint(style), success.paint(style), info.context);
+        println!("{}{} ({})", info.parser.display_name().paint(style), success.paint(style), info.context);
 
         #[cfg(not(feature = "color"))]
-        println!("{}{} ({})", info.parser.name, success, info.context);
+        println!("{}{} ({})", info.parser.display_name(), success, info.context);
 
         let children = self.get_children(node);
         let num_children = children.len();
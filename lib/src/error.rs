@@ -1,4 +1,6 @@
-use crate::input::{Show, ParserInfo};
+use std::fmt::{self, Display, Formatter};
+
+use crate::input::{Show, ParserInfo, Span, ByteSpan, ColumnUnit, SourceFile, SourceMap};
 
 pub use crate::expected::Expected;
 
@@ -47,10 +49,253 @@ impl<C: Show, E: std::fmt::Display> std::fmt::Display for ParseError<C, E> {
         #[cfg(feature = "color")] yansi::whenever(yansi::Condition::DEFAULT);
 
         for info in &self.stack {
-            write!(f, "\n + {}", info.parser.name)?;
+            write!(f, "\n + {}", info.parser.display_name())?;
             write!(f, " {}", &info.context as &dyn Show)?;
         }
 
         Ok(())
     }
 }
+
+/// Trait implemented by contexts that a [`Report`] can underline: spans over
+/// UTF-8 source with line/column positions, such as [`Span`] and [`ByteSpan`].
+pub trait SourceSpan {
+    /// The line, column, and offset at which the span starts.
+    fn start(&self) -> (usize, usize, usize);
+
+    /// The line, column, and offset at which the span ends.
+    fn end(&self) -> (usize, usize, usize);
+
+    /// The unit in which the span's columns are counted.
+    fn columns(&self) -> ColumnUnit;
+
+    /// The file the span is in, if it came from a [`SourceMap`].
+    fn file(&self) -> Option<SourceFile<'_>> {
+        None
+    }
+}
+
+impl SourceSpan for Span<'_> {
+    fn start(&self) -> (usize, usize, usize) { self.start }
+
+    fn end(&self) -> (usize, usize, usize) { self.end }
+
+    fn columns(&self) -> ColumnUnit { self.columns }
+
+    fn file(&self) -> Option<SourceFile<'_>> { self.file }
+}
+
+impl SourceSpan for ByteSpan<'_> {
+    fn start(&self) -> (usize, usize, usize) { self.start }
+
+    fn end(&self) -> (usize, usize, usize) { self.end }
+
+    fn columns(&self) -> ColumnUnit { ColumnUnit::Byte }
+}
+
+/// A compiler-style rendering of a [`ParseError`] whose context is a
+/// [`SourceSpan`], such as one from a [`Text`](crate::input::Text) input.
+///
+/// Where `ParseError`'s `Display` implementation fits the error on one line,
+/// a `Report` prints the offending line(s) of the original source with a
+/// line-number gutter, underlines the error's span with carets, one per
+/// column in the span's [`ColumnUnit`], and lists the parser stack as notes:
+///
+/// ```text
+/// error: expected token ']' but found '}'
+///  --> config.txt:2:5
+///   |
+/// 2 | [1,2}
+///   |     ^
+///   = note: in eat at config.txt:2:5
+///   = note: in list at config.txt:2:1
+/// ```
+///
+/// Offsets in a span are relative to the start of the input it came from, so
+/// the source passed to [`Report::new()`] must be that input's. For an input
+/// obtained from a [`SourceMap`], use [`Report::in_map()`] instead, which
+/// finds the source of the span's file in the map.
+///
+/// # Example
+///
+/// ```rust
+/// use pear::input::{Pear, Text};
+/// use pear::error::Report;
+/// use pear::parsers::*;
+///
+/// let source = "let x = 1;";
+/// let mut input = Pear::from(Text::from(source));
+/// eat_slice(&mut input, "let").unwrap();
+/// let err = eat(&mut input, '=').unwrap_err();
+///
+/// let report = Report::new(&err, source).color(false);
+/// assert_eq!(report.to_string(), "\
+/// error: expected token '=' but found ' '
+///  --> 1:4
+///   |
+/// 1 | let x = 1;
+///   |    ^
+///   = note: in eat at 1:4");
+/// ```
+pub struct Report<'r, C, E> {
+    error: &'r ParseError<C, E>,
+    source: &'r str,
+    base: usize,
+    color: bool,
+}
+
+impl<'r, C: SourceSpan, E> Report<'r, C, E> {
+    /// Creates a report of `error`, which occurred while parsing `source`.
+    ///
+    /// Output is colored if the `color` feature is enabled and `yansi`'s
+    /// global condition allows it. See [`color()`](Report::color()).
+    pub fn new(error: &'r ParseError<C, E>, source: &'r str) -> Self {
+        Report { error, source, base: 0, color: cfg!(feature = "color") }
+    }
+
+    /// Creates a report of `error`, which occurred while parsing a text from
+    /// `map`. The source is that of the file the error's span is in.
+    pub fn in_map(error: &'r ParseError<C, E>, map: &'r SourceMap) -> Self {
+        let context = &error.info.context;
+        let id = context.file().map(|file| file.id)
+            .or_else(|| map.file_at(context.start().2));
+
+        let (source, base) = id.map_or(("", 0), |id| (map.source(id), map.base(id)));
+        Report { error, source, base, color: cfg!(feature = "color") }
+    }
+
+    /// Sets whether the report is colored. Has no effect unless the `color`
+    /// feature is enabled.
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    // Converts a span offset into a byte index into `source`.
+    fn index(&self, offset: usize) -> usize {
+        let mut index = offset.saturating_sub(self.base).min(self.source.len());
+        while !self.source.is_char_boundary(index) {
+            index -= 1;
+        }
+
+        index
+    }
+
+    // Returns the byte range of the line containing byte `index`, excluding
+    // the line terminator.
+    fn line_at(&self, index: usize) -> (usize, usize) {
+        let start = self.source[..index].rfind('\n').map_or(0, |i| i + 1);
+        let end = self.source[index..].find('\n').map_or(self.source.len(), |i| index + i);
+        match self.source[start..end].ends_with('\r') {
+            true => (start, end - 1),
+            false => (start, end),
+        }
+    }
+
+    fn paint<T: Display>(&self, f: &mut Formatter<'_>, value: T, tone: Tone) -> fmt::Result {
+        #[cfg(feature = "color")]
+        if self.color {
+            use yansi::{Paint, Style, Color::*};
+
+            let style = match tone {
+                Tone::Error => Style::new().fg(Red).bold(),
+                Tone::Gutter => Style::new().fg(Blue).bold(),
+                Tone::Note => Style::new().bold(),
+            };
+
+            return write!(f, "{}", value.paint(style));
+        }
+
+        let _ = tone;
+        write!(f, "{}", value)
+    }
+
+    fn location(&self, f: &mut Formatter<'_>, span: &C) -> fmt::Result {
+        if let Some(file) = span.file() {
+            write!(f, "{}:", file.name)?;
+        }
+
+        write!(f, "{}:{}", span.start().0, span.start().1)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Tone {
+    Error,
+    Gutter,
+    Note,
+}
+
+impl<C: SourceSpan, E: Display> Display for Report<'_, C, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let span = &self.error.info.context;
+        let (span_start, span_end) = (span.start(), span.end());
+        let (start, end) = (self.index(span_start.2), self.index(span_end.2));
+        let width = span_end.0.max(span_start.0).to_string().len();
+        let unit = span.columns();
+        let gutter = |f: &mut Formatter<'_>, line: Option<usize>| {
+            match line {
+                Some(n) => self.paint(f, format_args!("{:>1$} |", n, width), Tone::Gutter),
+                None => self.paint(f, format_args!("{:>1$} |", "", width), Tone::Gutter),
+            }
+        };
+
+        self.paint(f, "error", Tone::Error)?;
+        write!(f, ": {}\n{:>2$}", self.error.error, "", width)?;
+        self.paint(f, "-->", Tone::Gutter)?;
+        write!(f, " ")?;
+        self.location(f, span)?;
+        writeln!(f)?;
+        gutter(f, None)?;
+
+        let mut line_start = self.line_at(start).0;
+        for number in span_start.0..=span_end.0.max(span_start.0) {
+            // A span ending at the start of a line doesn't cover that line.
+            let (from, to) = self.line_at(line_start);
+            if from == end && start != end {
+                break;
+            }
+
+            let line = &self.source[from..to];
+            writeln!(f)?;
+            gutter(f, Some(number))?;
+            if !line.is_empty() {
+                write!(f, " {}", line)?;
+            }
+
+            // The underlined portion of this line, in columns; a single
+            // caret marks an empty span.
+            let (a, b) = (start.clamp(from, to), end.clamp(from, to));
+            let columns = |i| unit.column(&self.source[from..i]);
+            let carets = match columns(b) - columns(a) {
+                0 if start == end => 1,
+                n => n,
+            };
+
+            if carets > 0 {
+                writeln!(f)?;
+                gutter(f, None)?;
+                write!(f, " ")?;
+                for c in self.source[from..a].chars() {
+                    write!(f, "{}", if c == '\t' { '\t' } else { ' ' })?;
+                }
+
+                self.paint(f, "^".repeat(carets), Tone::Error)?;
+            }
+
+            match self.source[to..].find('\n') {
+                Some(i) => line_start = to + i + 1,
+                None => break,
+            }
+        }
+
+        for info in &self.error.stack {
+            write!(f, "\n{:>1$} = ", "", width)?;
+            self.paint(f, "note", Tone::Note)?;
+            write!(f, ": in {} at ", info.parser.display_name())?;
+            self.location(f, &info.context)?;
+        }
+
+        Ok(())
+    }
+}
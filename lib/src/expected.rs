@@ -1,4 +1,6 @@
 use std::fmt;
+use std::io;
+use std::sync::Arc;
 
 use inlinable_string::InlinableString;
 
@@ -36,6 +38,12 @@ pub enum Expected<Token, Slice> {
     Token(Option<InlinableString>, Option<Token>),
     Slice(Option<InlinableString>, Option<Slice>),
     Eof(Option<Token>),
+    /// Any of several tokens or slices, described by the labels, would have
+    /// been accepted where the optional token was found. Reported when
+    /// [`Options::track_furthest`](crate::input::Options) is enabled.
+    OneOf(Vec<InlinableString>, Option<Token>),
+    Io(Arc<io::Error>),
+    Incomplete(usize),
     Other(CowInlineString),
     Elided
 }
@@ -49,6 +57,14 @@ impl<Token, Slice> Expected<Token, Slice> {
     pub fn eof(found: Option<Token>) -> Self {
         Expected::Eof(found)
     }
+
+    pub fn io(error: Arc<io::Error>) -> Self {
+        Expected::Io(error)
+    }
+
+    pub fn incomplete(needed: usize) -> Self {
+        Expected::Incomplete(needed)
+    }
 }
 
 impl<Token, Slice> Expected<Token, Slice> {
@@ -68,6 +84,9 @@ impl<Token, Slice> Expected<Token, Slice> {
             Token(e, v) => Token(e, v.map(t)),
             Slice(e, v) => Slice(e, v.map(s)),
             Eof(v) => Eof(v.map(t)),
+            OneOf(e, v) => OneOf(e, v.map(t)),
+            Io(v) => Io(v),
+            Incomplete(n) => Incomplete(n),
             Other(v) => Other(v),
             Expected::Elided => Expected::Elided,
         }
@@ -114,6 +133,15 @@ impl<T: fmt::Debug, S: fmt::Debug> fmt::Debug for Expected<T, S> {
             Expected::Eof(v) => {
                 f.debug_tuple("Expected::Eof").field(&v).finish()
             }
+            Expected::OneOf(e, v) => {
+                f.debug_tuple("Expected::OneOf").field(&e).field(&v).finish()
+            }
+            Expected::Io(v) => {
+                f.debug_tuple("Expected::Io").field(&v).finish()
+            }
+            Expected::Incomplete(n) => {
+                f.debug_tuple("Expected::Incomplete").field(&n).finish()
+            }
             Expected::Other(v) => {
                 f.debug_tuple("Expected::Other").field(&v).finish()
             }
@@ -128,6 +156,9 @@ impl<T: Clone, S: Clone> Clone for Expected<T, S> {
             Expected::Token(e, f) => Expected::Token(e.clone(), f.clone()),
             Expected::Slice(e, f) => Expected::Slice(e.clone(), f.clone()),
             Expected::Eof(f) => Expected::Eof(f.clone()),
+            Expected::OneOf(e, f) => Expected::OneOf(e.clone(), f.clone()),
+            Expected::Io(v) => Expected::Io(v.clone()),
+            Expected::Incomplete(n) => Expected::Incomplete(*n),
             Expected::Other(v) => Expected::Other(v.clone()),
             Expected::Elided => Expected::Elided,
         }
@@ -172,6 +203,27 @@ impl<T: Show, S: Show> fmt::Display for Expected<T, S> {
                 let found = found as &dyn Show;
                 write!(f, "unexpected token {}", found)
             }
+            Expected::OneOf(ref expected, ref found) => {
+                let one_of = if expected.len() == 1 { "" } else { "one of: " };
+                match found {
+                    Some(_) => write!(f, "expected {}", one_of)?,
+                    None => write!(f, "unexpected EOF: expected {}", one_of)?,
+                }
+
+                for (i, expected) in expected.iter().enumerate() {
+                    if i != 0 { write!(f, ", ")?; }
+                    write!(f, "{}", expected)?;
+                }
+
+                match found {
+                    Some(found) => write!(f, " but found {}", found as &dyn Show),
+                    None => Ok(()),
+                }
+            }
+            Expected::Io(ref error) => write!(f, "I/O error: {}", error),
+            Expected::Incomplete(needed) => {
+                write!(f, "incomplete input: at least {} more token(s) needed", needed)
+            }
             Expected::Other(ref other) => write!(f, "{}", other),
             Expected::Elided => write!(f, "[ERROR ELIDED]")
         }
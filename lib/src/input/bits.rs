@@ -0,0 +1,266 @@
+pub use crate::input::{Input, Rewind, Show, ParserInfo, Length};
+
+/// A run of bits from a [`Bits`] input.
+#[derive(Debug, Clone, Copy)]
+pub struct BitSlice<'a> {
+    bytes: &'a [u8],
+    start: usize,
+    len: usize,
+}
+
+impl<'a> BitSlice<'a> {
+    /// Returns the bit at index `i` of the slice, if there is one.
+    pub fn get(&self, i: usize) -> Option<bool> {
+        match i < self.len {
+            true => Some(bit(self.bytes, self.start + i)),
+            false => None,
+        }
+    }
+
+    /// Returns an iterator over the bits in the slice.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + 'a {
+        let (bytes, start) = (self.bytes, self.start);
+        (start..start + self.len).map(move |i| bit(bytes, i))
+    }
+
+    /// Returns the bits in the slice as an unsigned integer, most significant
+    /// bit first, or `None` if there are more than 64 bits.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.len <= 64 {
+            true => Some(self.iter().fold(0, |n, b| (n << 1) | u64::from(b))),
+            false => None,
+        }
+    }
+}
+
+impl PartialEq for BitSlice<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl Length for BitSlice<'_> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Show for BitSlice<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0b")?;
+        for b in self.iter() {
+            write!(f, "{}", b as u8)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A byte and bit offset into a [`Bits`] input. `bit` counts from the most
+/// significant bit of `byte`, so `0` is the most significant bit.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct BitOffset {
+    /// The offset of the byte.
+    pub byte: usize,
+    /// The offset of the bit within the byte.
+    pub bit: u8,
+}
+
+impl From<usize> for BitOffset {
+    fn from(offset: usize) -> Self {
+        BitOffset { byte: offset / 8, bit: (offset % 8) as u8 }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct BitSpan {
+    /// Start byte/bit offset.
+    pub start: BitOffset,
+    /// End byte/bit offset.
+    pub end: BitOffset,
+    /// Where the parser was pointing.
+    pub cursor: Option<bool>,
+}
+
+impl Show for BitSpan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (a, b) = (self.start.byte, self.start.bit);
+        let (c, d) = (self.end.byte, self.end.bit);
+        if self.start == self.end {
+            write!(f, "byte {} bit {}", a, b)?;
+        } else {
+            write!(f, "byte {} bit {} to byte {} bit {}", a, b, c, d)?;
+        }
+
+        match self.cursor {
+            Some(cursor) => write!(f, " [{}]", cursor as u8),
+            None => write!(f, " [EOF]"),
+        }
+    }
+}
+
+#[inline(always)]
+fn bit(bytes: &[u8], i: usize) -> bool {
+    bytes[i / 8] & (0x80 >> (i % 8)) != 0
+}
+
+/// A bit-level input over a byte slice for bit-packed formats. Bits are read
+/// from the most significant bit of each byte to the least significant.
+///
+/// Markers are bit offsets, so rewinding works at bit granularity. Contexts
+/// are [`BitSpan`]s, which report byte and bit offsets. See
+/// [`parsers::bits`](crate::parsers::bits) for parsers that read integers and
+/// realign to byte boundaries.
+#[derive(Debug)]
+pub struct Bits<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> From<&'a [u8]> for Bits<'a> {
+    fn from(bytes: &'a [u8]) -> Bits<'a> {
+        Bits { bytes, pos: 0 }
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for Bits<'a> {
+    fn from(bytes: &'a [u8; N]) -> Bits<'a> {
+        Bits::from(&bytes[..])
+    }
+}
+
+impl<'a> Bits<'a> {
+    /// Returns the offset of the next bit to be read.
+    pub fn offset(&self) -> BitOffset {
+        BitOffset::from(self.pos)
+    }
+
+    /// Skips to the next byte boundary, if not already at one. Returns the
+    /// number of bits skipped.
+    pub fn align(&mut self) -> usize {
+        let skipped = (8 - self.pos % 8) % 8;
+        self.pos = std::cmp::min(self.pos + skipped, self.bits());
+        skipped
+    }
+
+    fn bits(&self) -> usize {
+        self.bytes.len() * 8
+    }
+
+    fn remaining(&self) -> usize {
+        self.bits() - self.pos
+    }
+}
+
+impl<'a> Input for Bits<'a> {
+    type Token = bool;
+    type Slice = BitSlice<'a>;
+    type Many = Self::Slice;
+
+    type Marker = usize;
+    type Context = BitSpan;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        match self.pos < self.bits() {
+            true => Some(bit(self.bytes, self.pos)),
+            false => None,
+        }
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        match n <= self.remaining() {
+            true => Some(BitSlice { bytes: self.bytes, start: self.pos, len: n }),
+            false => None,
+        }
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.token()?;
+        if cond(&token) {
+            self.pos += 1;
+            return Some(token);
+        }
+
+        None
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.pos += n;
+            return Some(slice);
+        }
+
+        None
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let start = self.pos;
+        while self.eat(&mut cond).is_some() { }
+        BitSlice { bytes: self.bytes, start, len: self.pos - start }
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.take(cond).len()
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.remaining() >= n
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.pos
+    }
+
+    fn progress(&mut self) -> Option<usize> {
+        Some(self.pos)
+    }
+
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        BitSpan {
+            start: BitOffset::from(mark),
+            end: BitOffset::from(self.pos),
+            cursor: self.token(),
+        }
+    }
+}
+
+impl Rewind for Bits<'_> {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.pos = marker;
+    }
+}
@@ -0,0 +1,194 @@
+use std::sync::OnceLock;
+
+pub use crate::input::{Input, Rewind, Seek, Show, ParserInfo};
+use crate::input::line_index::LineIndex;
+use crate::input::text::show_span;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ByteSpan<'a> {
+    /// Start line/column/offset.
+    pub start: (usize, usize, usize),
+    /// End line/column/offset.
+    pub end: (usize, usize, usize),
+    /// Where the parser was pointing.
+    pub cursor: Option<u8>,
+    /// Snippet between start and end.
+    pub snippet: Option<&'a [u8]>,
+}
+
+impl<'a> Show for ByteSpan<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cursor = self.cursor.map(|c| c.escape_ascii());
+        show_span(f, None, self.start, self.end, self.snippet, cursor)
+    }
+}
+
+/// A byte-oriented input akin to [`Text`](crate::input::Text) whose contexts
+/// report lines and columns, counted in bytes, for mostly-ASCII data.
+#[derive(Debug)]
+pub struct Bytes<'a> {
+    current: &'a [u8],
+    start: &'a [u8],
+    lines: OnceLock<LineIndex>,
+}
+
+impl<'a> From<&'a [u8]> for Bytes<'a> {
+    fn from(start: &'a [u8]) -> Bytes<'a> {
+        Bytes { start, current: start, lines: OnceLock::new() }
+    }
+}
+
+impl<'a> From<&'a str> for Bytes<'a> {
+    fn from(start: &'a str) -> Bytes<'a> {
+        Bytes::from(start.as_bytes())
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for Bytes<'a> {
+    fn from(start: &'a [u8; N]) -> Bytes<'a> {
+        Bytes::from(&start[..])
+    }
+}
+
+impl<'a> Bytes<'a> {
+    /// Returns the 1-indexed line and column, counted in bytes, of the byte at
+    /// `offset`. Like [`Text::position()`](crate::input::Text::position()),
+    /// lookups are binary searches over a lazily built line table.
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        self.lines
+            .get_or_init(|| LineIndex::new(self.start))
+            .position(offset)
+    }
+}
+
+impl Rewind for Bytes<'_> {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.current = &self.start[marker..];
+    }
+}
+
+impl Seek for Bytes<'_> {
+    fn seek_to(&mut self, offset: usize) -> bool {
+        match self.start.get(offset..) {
+            Some(current) => {
+                self.current = current;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<'a> Input for Bytes<'a> {
+    type Token = u8;
+    type Slice = &'a [u8];
+    type Many = Self::Slice;
+
+    type Marker = usize;
+    type Context = ByteSpan<'a>;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.current.first().copied()
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.current.get(..n)
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.token()?;
+        if cond(&token) {
+            self.current = &self.current[1..];
+            return Some(token);
+        }
+
+        None
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.current = &self.current[n..];
+            return Some(slice);
+        }
+
+        None
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let consumed = self.current.iter()
+            .position(|b| !cond(b))
+            .unwrap_or(self.current.len());
+
+        let value = &self.current[..consumed];
+        self.current = &self.current[consumed..];
+        value
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.take(cond).len()
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.current.len() >= n
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.start.len() - self.current.len()
+    }
+
+    fn progress(&mut self) -> Option<usize> {
+        Some(self.start.len() - self.current.len())
+    }
+
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        let cursor = self.token();
+        let bytes_read = self.start.len() - self.current.len();
+        if bytes_read == 0 {
+            ByteSpan { start: (1, 1, 0), end: (1, 1, 0), snippet: None, cursor }
+        } else {
+            let (start_line, start_col) = self.position(mark);
+            let (end_line, end_col) = self.position(bytes_read);
+            ByteSpan {
+                start: (start_line, start_col, mark),
+                end: (end_line, end_col, bytes_read),
+                snippet: self.start.get(mark..bytes_read),
+                cursor,
+            }
+        }
+    }
+}
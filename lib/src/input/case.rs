@@ -0,0 +1,67 @@
+use crate::input::Extent;
+
+/// Trait implemented by tokens and slices that can be compared to an `Rhs`
+/// without regard to ASCII case, as by
+/// [`eat_ignore_case()`](crate::parsers::eat_ignore_case()) and
+/// [`eat_slice_ignore_case()`](crate::parsers::eat_slice_ignore_case()).
+pub trait IgnoreCase<Rhs: ?Sized = Self> {
+    /// Returns `true` if `self` and `other` are equal when ASCII letters are
+    /// compared without regard to case.
+    fn eq_ignore_ascii_case(&self, other: &Rhs) -> bool;
+}
+
+impl IgnoreCase for char {
+    #[inline(always)]
+    fn eq_ignore_ascii_case(&self, other: &char) -> bool {
+        char::eq_ignore_ascii_case(self, other)
+    }
+}
+
+impl IgnoreCase for u8 {
+    #[inline(always)]
+    fn eq_ignore_ascii_case(&self, other: &u8) -> bool {
+        u8::eq_ignore_ascii_case(self, other)
+    }
+}
+
+impl IgnoreCase<&str> for &str {
+    #[inline(always)]
+    fn eq_ignore_ascii_case(&self, other: &&str) -> bool {
+        str::eq_ignore_ascii_case(self, other)
+    }
+}
+
+impl IgnoreCase<&str> for Extent<&str> {
+    #[inline(always)]
+    fn eq_ignore_ascii_case(&self, other: &&str) -> bool {
+        str::eq_ignore_ascii_case(self.values, other)
+    }
+}
+
+impl IgnoreCase<&[u8]> for &[u8] {
+    #[inline(always)]
+    fn eq_ignore_ascii_case(&self, other: &&[u8]) -> bool {
+        <[u8]>::eq_ignore_ascii_case(self, other)
+    }
+}
+
+impl IgnoreCase<&[u8]> for Extent<&[u8]> {
+    #[inline(always)]
+    fn eq_ignore_ascii_case(&self, other: &&[u8]) -> bool {
+        <[u8]>::eq_ignore_ascii_case(self.values, other)
+    }
+}
+
+impl<const N: usize> IgnoreCase<&[u8; N]> for &[u8] {
+    #[inline(always)]
+    fn eq_ignore_ascii_case(&self, other: &&[u8; N]) -> bool {
+        <[u8]>::eq_ignore_ascii_case(self, &other[..])
+    }
+}
+
+impl<const N: usize> IgnoreCase<&[u8; N]> for Extent<&[u8]> {
+    #[inline(always)]
+    fn eq_ignore_ascii_case(&self, other: &&[u8; N]) -> bool {
+        <[u8]>::eq_ignore_ascii_case(self.values, &other[..])
+    }
+}
@@ -0,0 +1,333 @@
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+use crate::input::{Input, Rewind, Seek, Show, ParserInfo, Length, Span, ByteSpan};
+use crate::input::shared::Buffer;
+use crate::input::line_index::LineIndex;
+
+/// Trait implemented by the chunks a [`Chunks`] input can parse: `str`, with
+/// `char` tokens and [`Span`] contexts, and `[u8]`, with `u8` tokens and
+/// [`ByteSpan`] contexts.
+pub trait Chunk: Buffer + Length + ToOwned {
+    /// The type of the input's contexts.
+    type Span<'a>: Show where Self: 'a;
+
+    #[doc(hidden)]
+    fn empty() -> &'static Self;
+
+    #[doc(hidden)]
+    fn join(pieces: &[&Self]) -> Self::Owned;
+
+    #[doc(hidden)]
+    fn span<'a>(
+        start: (usize, usize, usize),
+        end: (usize, usize, usize),
+        cursor: Option<Self::Token>,
+        snippet: Option<&'a Self>,
+    ) -> Self::Span<'a>;
+}
+
+impl Chunk for str {
+    type Span<'a> = Span<'a>;
+
+    fn empty() -> &'static Self {
+        ""
+    }
+
+    fn join(pieces: &[&Self]) -> String {
+        pieces.concat()
+    }
+
+    fn span<'a>(
+        start: (usize, usize, usize),
+        end: (usize, usize, usize),
+        cursor: Option<char>,
+        snippet: Option<&'a str>,
+    ) -> Span<'a> {
+        Span::new(start, end, cursor, snippet)
+    }
+}
+
+impl Chunk for [u8] {
+    type Span<'a> = ByteSpan<'a>;
+
+    fn empty() -> &'static Self {
+        &[]
+    }
+
+    fn join(pieces: &[&Self]) -> Vec<u8> {
+        pieces.concat()
+    }
+
+    fn span<'a>(
+        start: (usize, usize, usize),
+        end: (usize, usize, usize),
+        cursor: Option<u8>,
+        snippet: Option<&'a [u8]>,
+    ) -> ByteSpan<'a> {
+        ByteSpan { start, end, cursor, snippet }
+    }
+}
+
+/// An input over a sequence of borrowed chunks, such as the pieces of a rope
+/// or a chain of network buffers, parsed as if they were one contiguous
+/// buffer.
+///
+/// Tokens, slices, `take`, and markers all work across chunk boundaries.
+/// Markers are absolute offsets, in bytes, into the concatenation of the
+/// chunks. Slices and `Many`s are [`Cow`]s: borrowed from a chunk when they
+/// lie entirely within one and copied into an owned buffer only when they span
+/// several. A slice of a `Chunks<str>` can't split a `char`, just as a slice
+/// of a `str` can't, so chunks of a `Chunks<str>` should break at `char`
+/// boundaries, as they necessarily do if each chunk is a `&str`.
+///
+/// Contexts are [`Span`]s for `Chunks<str>` and [`ByteSpan`]s for
+/// `Chunks<[u8]>`, with lines and columns counted in bytes. Their snippets are
+/// only present when the span lies within a single chunk.
+///
+/// # Example
+///
+/// ```rust
+/// use pear::input::{Pear, Chunks};
+/// use pear::parsers::*;
+///
+/// let mut input = Pear::from(Chunks::new(["hel", "lo wo", "rld"]));
+/// assert_eq!(take_while(&mut input, |c| c.is_alphabetic()).unwrap(), "hello");
+/// assert_eq!(eat_slice(&mut input, " world").unwrap(), " world");
+/// ```
+#[derive(Debug)]
+pub struct Chunks<'a, T: ?Sized + Chunk> {
+    chunks: Vec<&'a T>,
+    // `starts[i]` is the offset of the first byte of `chunks[i]`.
+    starts: Vec<usize>,
+    len: usize,
+    pos: usize,
+    // The index of the chunk containing `pos`, or `chunks.len()` at the end.
+    index: usize,
+    lines: OnceLock<LineIndex>,
+}
+
+impl<'a, T: ?Sized + Chunk> Chunks<'a, T> {
+    /// Creates an input over the concatenation of `chunks`.
+    pub fn new<C>(chunks: C) -> Self
+        where C: IntoIterator<Item = &'a T>
+    {
+        let chunks: Vec<&'a T> = chunks.into_iter()
+            .filter(|chunk| chunk.buffer_len() > 0)
+            .collect();
+
+        let mut len = 0;
+        let starts = chunks.iter()
+            .map(|chunk| { len += chunk.buffer_len(); len - chunk.buffer_len() })
+            .collect();
+
+        Chunks { chunks, starts, len, pos: 0, index: 0, lines: OnceLock::new() }
+    }
+
+    /// Returns the number of chunks.
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns the 1-indexed line and column, counted in bytes, of the byte at
+    /// `offset`.
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        self.lines
+            .get_or_init(|| LineIndex::from_chunks(self.chunks.iter().map(|c| c.bytes())))
+            .position(offset)
+    }
+
+    /// Returns the index of the chunk containing `offset`, or the number of
+    /// chunks if `offset` is the end of the input.
+    fn locate(&self, offset: usize) -> usize {
+        if offset >= self.len {
+            return self.chunks.len();
+        }
+
+        self.starts.partition_point(|&start| start <= offset) - 1
+    }
+
+    /// Returns the bytes in `start..end`, borrowed if they lie in one chunk.
+    /// Returns `None` if the range is out of bounds or splits a token.
+    fn between(&self, start: usize, end: usize) -> Option<Cow<'a, T>> {
+        if start > end || end > self.len {
+            return None;
+        } else if start == end {
+            return Some(Cow::Borrowed(T::empty()));
+        }
+
+        let first = self.locate(start);
+        let mut pieces = Vec::new();
+        for (chunk, &offset) in self.chunks[first..].iter().zip(&self.starts[first..]) {
+            let from = start.saturating_sub(offset);
+            let to = std::cmp::min(end - offset, chunk.buffer_len());
+            pieces.push(chunk.range(from..to)?);
+            if offset + to == end {
+                break;
+            }
+        }
+
+        match pieces.as_slice() {
+            [piece] => Some(Cow::Borrowed(*piece)),
+            pieces => Some(Cow::Owned(T::join(pieces))),
+        }
+    }
+
+    fn advance(&mut self, n: usize) {
+        self.pos += n;
+        while self.index < self.chunks.len()
+            && self.pos >= self.starts[self.index] + self.chunks[self.index].buffer_len()
+        {
+            self.index += 1;
+        }
+    }
+}
+
+impl<'a, T: ?Sized + Chunk> Input for Chunks<'a, T>
+    where Cow<'a, T>: Show
+{
+    type Token = T::Token;
+    type Slice = Cow<'a, T>;
+    type Many = Self::Slice;
+
+    type Marker = usize;
+    type Context = T::Span<'a>;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        let chunk = self.chunks.get(self.index)?;
+        let offset = self.pos - self.starts[self.index];
+        chunk.range(offset..chunk.buffer_len())?.first_token().map(|(token, _)| token)
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.between(self.pos, self.pos.checked_add(n)?)
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let chunk = self.chunks.get(self.index)?;
+        let offset = self.pos - self.starts[self.index];
+        let (token, len) = chunk.range(offset..chunk.buffer_len())?.first_token()?;
+        if cond(&token) {
+            self.advance(len);
+            return Some(token);
+        }
+
+        None
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.advance(n);
+            return Some(slice);
+        }
+
+        None
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let start = self.pos;
+        while self.eat(&mut cond).is_some() { }
+        self.between(start, self.pos).expect("tokens are in bounds")
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let mut skipped = 0;
+        while self.eat(&mut cond).is_some() {
+            skipped += 1;
+        }
+
+        skipped
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.len - self.pos >= n
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.pos
+    }
+
+    fn progress(&mut self) -> Option<usize> {
+        Some(self.pos)
+    }
+
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        let cursor = self.token();
+        if self.pos == 0 {
+            return T::span((1, 1, 0), (1, 1, 0), cursor, None);
+        }
+
+        let (start_line, start_col) = self.position(mark);
+        let (end_line, end_col) = self.position(self.pos);
+        let snippet = match self.between(mark, self.pos) {
+            Some(Cow::Borrowed(snippet)) => Some(snippet),
+            _ => None,
+        };
+
+        let start = (start_line, start_col, mark);
+        let end = (end_line, end_col, self.pos);
+        T::span(start, end, cursor, snippet)
+    }
+}
+
+impl<'a, T: ?Sized + Chunk> Rewind for Chunks<'a, T>
+    where Cow<'a, T>: Show
+{
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.pos = marker;
+        self.index = self.locate(marker);
+    }
+}
+
+impl<'a, T: ?Sized + Chunk> Seek for Chunks<'a, T>
+    where Cow<'a, T>: Show
+{
+    fn seek_to(&mut self, offset: usize) -> bool {
+        let index = self.locate(offset);
+        if let Some(chunk) = self.chunks.get(index) {
+            if chunk.range(offset - self.starts[index]..chunk.buffer_len()).is_none() {
+                return false;
+            }
+        } else if offset > self.len {
+            return false;
+        }
+
+        self.rewind_to(offset);
+        true
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use crate::input::{Input, Show, Rewind, ParserInfo, Length};
+use crate::input::{Input, Show, Rewind, Seek, ParserInfo, Length};
 
 #[derive(Debug)]
 pub struct Cursor<T> {
@@ -53,6 +53,12 @@ impl<T: PartialEq> PartialEq<Extent<&[T]>> for &[T] {
     }
 }
 
+impl<T: PartialEq> PartialEq<Extent<Vec<T>>> for &[T] {
+    fn eq(&self, other: &Extent<Vec<T>>) -> bool {
+        &other.values[..] == *self
+    }
+}
+
 macro_rules! impl_for_slice_len {
     ($($n:expr),*) => ($(
         impl<T: PartialEq> PartialEq<Extent<&[T]>> for &[T; $n] {
@@ -60,6 +66,12 @@ macro_rules! impl_for_slice_len {
                 &other.values[..] == *self
             }
         }
+
+        impl<T: PartialEq> PartialEq<Extent<Vec<T>>> for &[T; $n] {
+            fn eq(&self, other: &Extent<Vec<T>>) -> bool {
+                &other.values[..] == *self
+            }
+        }
     )*)
 }
 
@@ -275,6 +287,10 @@ impl<T: Indexable + Show + Length + PartialEq> Input for Cursor<T>
         self.offset()
     }
 
+    fn progress(&mut self) -> Option<usize> {
+        Some(self.offset())
+    }
+
     /// Optionally returns a context to identify the current input position. By
     /// default, this method returns `None`, indicating that no context could be
     /// resolved.
@@ -292,3 +308,17 @@ impl<T: Indexable + Show + Length + PartialEq> Rewind for Cursor<T>
         self.items = self.start.slice(marker..).unwrap();
     }
 }
+
+impl<T: Indexable + Show + Length + PartialEq> Seek for Cursor<T>
+    where T::One: Show + PartialEq
+{
+    fn seek_to(&mut self, offset: usize) -> bool {
+        match self.start.slice(offset..) {
+            Some(items) => {
+                self.items = items;
+                true
+            }
+            None => false,
+        }
+    }
+}
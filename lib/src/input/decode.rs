@@ -0,0 +1,512 @@
+use std::fmt;
+use std::io;
+use std::sync::Arc;
+
+use crate::input::{Input, Rewind, Show, ParserInfo};
+
+const INFO: ParserInfo = ParserInfo::new("decoded", true);
+
+/// Trait implemented by decoders for a [`Decoded`] input.
+pub trait Decoder<I: Input> {
+    /// The type of the decoded tokens.
+    type Token: Show + PartialEq + Clone;
+
+    /// Consumes the encoding of the next group of decoded tokens from `input`
+    /// and pushes the tokens to `tokens`. Called only when `input` has at least
+    /// one token. Returns an error message if `input` doesn't begin with a
+    /// valid encoding.
+    fn decode(
+        &mut self,
+        input: &mut I,
+        tokens: &mut Vec<Self::Token>
+    ) -> std::result::Result<(), &'static str>;
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for char {}
+}
+
+/// Trait implemented by the tokens of text-based encodings: `u8` and `char`.
+pub trait Encoded: private::Sealed + Copy {
+    #[doc(hidden)]
+    fn ascii(self) -> Option<u8>;
+
+    #[doc(hidden)]
+    fn push_utf8(self, bytes: &mut Vec<u8>);
+}
+
+impl Encoded for u8 {
+    fn ascii(self) -> Option<u8> {
+        self.is_ascii().then_some(self)
+    }
+
+    fn push_utf8(self, bytes: &mut Vec<u8>) {
+        bytes.push(self)
+    }
+}
+
+impl Encoded for char {
+    fn ascii(self) -> Option<u8> {
+        self.is_ascii().then_some(self as u8)
+    }
+
+    fn push_utf8(self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(self.encode_utf8(&mut [0; 4]).as_bytes())
+    }
+}
+
+/// Decodes percent-encoded text, as in URIs, into bytes. Characters other than
+/// `%XX` escapes decode to their UTF-8 encoding.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Percent {
+    form: bool,
+}
+
+impl Percent {
+    /// Decodes percent-encoding as in URIs.
+    pub fn new() -> Self {
+        Percent { form: false }
+    }
+
+    /// Decodes percent-encoding as in `application/x-www-form-urlencoded`
+    /// data, where `+` also encodes a space.
+    pub fn form() -> Self {
+        Percent { form: true }
+    }
+}
+
+impl<I: Input> Decoder<I> for Percent where I::Token: Encoded {
+    type Token = u8;
+
+    fn decode(
+        &mut self,
+        input: &mut I,
+        bytes: &mut Vec<u8>
+    ) -> std::result::Result<(), &'static str> {
+        fn hex<I: Input>(input: &mut I) -> Option<u8> where I::Token: Encoded {
+            let digit = |t: &I::Token| t.ascii().and_then(|b| (b as char).to_digit(16));
+            input.eat(|t| digit(t).is_some()).and_then(|t| digit(&t)).map(|d| d as u8)
+        }
+
+        let token = input.eat(|_| true).ok_or("missing token")?;
+        match token.ascii() {
+            Some(b'%') => match (hex(input), hex(input)) {
+                (Some(hi), Some(lo)) => bytes.push(hi << 4 | lo),
+                _ => return Err("invalid percent-encoding"),
+            },
+            Some(b'+') if self.form => bytes.push(b' '),
+            _ => token.push_utf8(bytes),
+        }
+
+        Ok(())
+    }
+}
+
+/// Decodes base64, with or without padding, into bytes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Base64 {
+    url_safe: bool,
+}
+
+impl Base64 {
+    /// Decodes base64 with the standard alphabet, which uses `+` and `/`.
+    pub fn standard() -> Self {
+        Base64 { url_safe: false }
+    }
+
+    /// Decodes base64 with the URL- and filename-safe alphabet, which uses
+    /// `-` and `_`.
+    pub fn url_safe() -> Self {
+        Base64 { url_safe: true }
+    }
+
+    fn value(&self, byte: u8) -> Option<u32> {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' if !self.url_safe => 62,
+            b'/' if !self.url_safe => 63,
+            b'-' if self.url_safe => 62,
+            b'_' if self.url_safe => 63,
+            _ => return None,
+        };
+
+        Some(value.into())
+    }
+}
+
+impl<I: Input> Decoder<I> for Base64 where I::Token: Encoded {
+    type Token = u8;
+
+    fn decode(
+        &mut self,
+        input: &mut I,
+        bytes: &mut Vec<u8>
+    ) -> std::result::Result<(), &'static str> {
+        let value = |t: &I::Token| t.ascii().and_then(|b| self.value(b));
+        let (mut bits, mut n) = (0u32, 0);
+        while n < 4 {
+            match input.eat(|t| value(t).is_some()).and_then(|t| value(&t)) {
+                Some(v) => bits = bits << 6 | v,
+                None => break,
+            }
+
+            n += 1;
+        }
+
+        let mut padding = 0;
+        while n + padding < 4 && input.eat(|t| t.ascii() == Some(b'=')).is_some() {
+            padding += 1;
+        }
+
+        match (n, padding) {
+            (0, 0) => return Err("invalid base64 character"),
+            (0, _) => return Err("invalid base64 padding"),
+            (1, _) => return Err("truncated base64"),
+            (n, p) if p > 0 && n + p != 4 => return Err("invalid base64 padding"),
+            _ => {}
+        }
+
+        let len = n * 3 / 4;
+        let bits = bits << (6 * (4 - n));
+        bytes.extend_from_slice(&bits.to_be_bytes()[1..(1 + len)]);
+        Ok(())
+    }
+}
+
+/// Decodes UTF-16 bytes into `char`s.
+#[derive(Debug, Clone, Copy)]
+pub struct Utf16 {
+    big_endian: bool,
+}
+
+impl Utf16 {
+    /// Decodes little-endian UTF-16.
+    pub fn le() -> Self {
+        Utf16 { big_endian: false }
+    }
+
+    /// Decodes big-endian UTF-16.
+    pub fn be() -> Self {
+        Utf16 { big_endian: true }
+    }
+
+    fn unit<I: Input<Token = u8>>(&self, input: &mut I) -> Option<u16> {
+        if !input.has(2) {
+            return None;
+        }
+
+        let bytes = [input.eat(|_| true)?, input.eat(|_| true)?];
+        match self.big_endian {
+            true => Some(u16::from_be_bytes(bytes)),
+            false => Some(u16::from_le_bytes(bytes)),
+        }
+    }
+}
+
+impl<I: Input<Token = u8>> Decoder<I> for Utf16 {
+    type Token = char;
+
+    fn decode(
+        &mut self,
+        input: &mut I,
+        chars: &mut Vec<char>
+    ) -> std::result::Result<(), &'static str> {
+        let first = self.unit(input).ok_or("truncated UTF-16 code unit")?;
+        let units = match (0xD800..0xDC00).contains(&first) {
+            true => [first, self.unit(input).ok_or("unpaired UTF-16 surrogate")?],
+            false => [first, 0],
+        };
+
+        let c = char::decode_utf16(units).next().expect("at least one unit");
+        chars.push(c.map_err(|_| "unpaired UTF-16 surrogate")?);
+        Ok(())
+    }
+}
+
+struct Group<M, T> {
+    // Markers in the underlying input to the start and end of the encoding.
+    start: M,
+    end: M,
+    tokens: Vec<T>,
+    // The index of the current token. Always less than `tokens.len()`.
+    index: usize,
+}
+
+/// An input adapter that lazily decodes an encoded input `I`, such as
+/// percent-encoded text, base64, or UTF-16, with a [`Decoder`] `D`.
+///
+/// Tokens are decoded on demand, one encoded group at a time, so the input is
+/// never decoded into a temporary buffer. Contexts are `I`'s own, and markers
+/// are `I`'s markers paired with an index into a group, so errors point at
+/// the encoded source the user wrote, not at the decoded text. If the source
+/// isn't validly encoded, the decoded input ends at the invalid encoding and
+/// [`Input::invalid()`] returns the decoder's message, so parsers that fail
+/// there report it as an [`Expected::Other`](crate::error::Expected::Other)
+/// whose context's cursor is the start of the invalid encoding.
+///
+/// Slices and `Many`s are `Vec`s of decoded tokens.
+///
+/// # Example
+///
+/// ```rust
+/// use pear::input::{Pear, Text, Decoded, Percent};
+/// use pear::parsers::*;
+///
+/// let decoded = Decoded::new(Text::from("caf%C3%A9%20au%20lait"), Percent::new());
+/// let mut input = Pear::from(decoded);
+/// let bytes = take_while(&mut input, |_| true).unwrap();
+/// assert_eq!(String::from_utf8(bytes).unwrap(), "café au lait");
+/// ```
+pub struct Decoded<I: Input, D: Decoder<I>> {
+    input: I,
+    decoder: D,
+    group: Option<Group<I::Marker, D::Token>>,
+    invalid: Option<&'static str>,
+}
+
+impl<I: Input + fmt::Debug, D: Decoder<I> + fmt::Debug> fmt::Debug for Decoded<I, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Decoded")
+            .field("input", &self.input)
+            .field("decoder", &self.decoder)
+            .field("invalid", &self.invalid)
+            .finish()
+    }
+}
+
+impl<I: Input + Rewind, D: Decoder<I>> Decoded<I, D> {
+    /// Wraps `input`, decoding it with `decoder`.
+    pub fn new(input: I, decoder: D) -> Self {
+        Decoded { input, decoder, group: None, invalid: None }
+    }
+
+    /// Returns a reference to the underlying encoded input.
+    pub fn get_ref(&self) -> &I {
+        &self.input
+    }
+
+    /// Consumes `self`, returning the underlying encoded input, positioned
+    /// after the encoding of the last consumed group.
+    pub fn into_inner(mut self) -> I {
+        self.release();
+        self.input
+    }
+
+    /// Decodes the next group if the current one has been consumed. Returns
+    /// `true` if there's a current token.
+    fn load(&mut self) -> bool {
+        if self.group.is_some() {
+            return true;
+        } else if self.invalid.is_some() || !self.input.has(1) {
+            return false;
+        }
+
+        let start = self.input.mark(&INFO);
+        let mut tokens = vec![];
+        match self.decoder.decode(&mut self.input, &mut tokens) {
+            Ok(()) if !tokens.is_empty() => {
+                let end = self.input.mark(&INFO);
+                self.group = Some(Group { start, end, tokens, index: 0 });
+                true
+            }
+            result => {
+                self.invalid = result.err();
+                self.input.rewind_to(start);
+                self.input.unmark(start);
+                false
+            }
+        }
+    }
+
+    fn release(&mut self) {
+        if let Some(group) = self.group.take() {
+            self.input.unmark(group.start);
+            self.input.unmark(group.end);
+        }
+    }
+
+    fn advance(&mut self) {
+        if let Some(group) = self.group.as_mut() {
+            group.index += 1;
+            if group.index == group.tokens.len() {
+                self.release();
+            }
+        }
+    }
+
+    /// Runs `f` with the underlying input at the start of the current group.
+    fn at_group_start<T, F: FnOnce(&mut I) -> T>(&mut self, f: F) -> T {
+        match self.group {
+            Some(Group { start, end, .. }) => {
+                self.input.rewind_to(start);
+                let value = f(&mut self.input);
+                self.input.rewind_to(end);
+                value
+            }
+            None => f(&mut self.input),
+        }
+    }
+}
+
+impl<I: Input + Rewind, D: Decoder<I>> Input for Decoded<I, D> {
+    type Token = D::Token;
+    type Slice = Vec<D::Token>;
+    type Many = Vec<D::Token>;
+
+    type Marker = (I::Marker, usize);
+    type Context = I::Context;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        match self.load() {
+            true => self.group.as_ref().map(|g| g.tokens[g.index].clone()),
+            false => None,
+        }
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        let start = self.mark(&INFO);
+        let mut slice = Vec::with_capacity(n);
+        while slice.len() < n {
+            match self.eat(|_| true) {
+                Some(token) => slice.push(token),
+                None => break,
+            }
+        }
+
+        self.rewind_to(start);
+        self.unmark(start);
+        (slice.len() == n).then_some(slice)
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.token()?;
+        if cond(&token) {
+            self.advance();
+            return Some(token);
+        }
+
+        None
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            for _ in 0..n {
+                self.eat(|_| true);
+            }
+
+            return Some(slice);
+        }
+
+        None
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let mut many = vec![];
+        while let Some(token) = self.eat(&mut cond) {
+            many.push(token);
+        }
+
+        many
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let mut skipped = 0;
+        while self.eat(&mut cond).is_some() {
+            skipped += 1;
+        }
+
+        skipped
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.slice(n).is_some()
+    }
+
+    fn mark(&mut self, info: &ParserInfo) -> Self::Marker {
+        let index = self.group.as_ref().map_or(0, |g| g.index);
+        (self.at_group_start(|input| input.mark(info)), index)
+    }
+
+    fn context(&mut self, (mark, _): Self::Marker) -> Self::Context {
+        self.at_group_start(|input| input.context(mark))
+    }
+
+    #[inline(always)]
+    fn unmark(&mut self, (mark, _): Self::Marker) {
+        self.input.unmark(mark)
+    }
+
+    /// The progress of the underlying input at the start of the current group
+    /// plus the index into the group.
+    fn progress(&mut self) -> Option<usize> {
+        let index = self.group.as_ref().map_or(0, |g| g.index);
+        self.at_group_start(|input| input.progress()).map(|p| p + index)
+    }
+
+    #[inline(always)]
+    fn io_error(&mut self) -> Option<Arc<io::Error>> {
+        self.input.io_error()
+    }
+
+    fn invalid(&mut self) -> Option<&'static str> {
+        self.load();
+        self.invalid.or_else(|| self.input.invalid())
+    }
+
+    #[inline(always)]
+    fn is_complete(&mut self) -> bool {
+        self.input.is_complete()
+    }
+}
+
+impl<I: Input + Rewind, D: Decoder<I>> Rewind for Decoded<I, D> {
+    fn rewind_to(&mut self, (mark, index): Self::Marker) {
+        self.release();
+        self.invalid = None;
+        self.input.rewind_to(mark);
+        if index > 0 && self.load() {
+            if let Some(group) = self.group.as_mut() {
+                group.index = index;
+            }
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use std::io;
+use std::sync::Arc;
+
 use crate::input::{Show, Length};
 
 pub trait Token<I: Input>: Show + PartialEq<I::Token> { }
@@ -9,9 +12,30 @@ impl<I: Input, T> Token<I> for T where T: Show + PartialEq<I::Token> { }
 impl<I: Input, S> Slice<I> for S where S: Show + Length + PartialEq<I::Slice> { }
 
 #[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
 pub struct ParserInfo {
     pub name: &'static str,
     pub raw: bool,
+    /// A human-readable description of what the parser parses, as set by
+    /// `#[parser(label = "...")]`.
+    pub label: Option<&'static str>,
+}
+
+impl ParserInfo {
+    /// Returns the information for an unlabeled parser named `name`.
+    pub const fn new(name: &'static str, raw: bool) -> Self {
+        ParserInfo { name, raw, label: None }
+    }
+
+    /// Returns `self` with its label set to `label`.
+    pub const fn with_label(self, label: &'static str) -> Self {
+        ParserInfo { label: Some(label), ..self }
+    }
+
+    /// Returns the parser's label, if it has one, or else its name.
+    pub fn display_name(&self) -> &'static str {
+        self.label.unwrap_or(self.name)
+    }
 }
 
 pub trait Rewind: Sized + Input {
@@ -19,6 +43,15 @@ pub trait Rewind: Sized + Input {
     fn rewind_to(&mut self, marker: Self::Marker);
 }
 
+/// Trait implemented by inputs that can move to an arbitrary position, such as
+/// one read from an offset table, not just to a previously emitted marker.
+pub trait Seek: Rewind {
+    /// Moves `self` to the absolute position `offset`, measured in the same
+    /// units as the lengths of slices from [`Input::slice()`]. Returns `false`
+    /// without moving if `offset` isn't a valid position in the input.
+    fn seek_to(&mut self, offset: usize) -> bool;
+}
+
 pub trait Input: Sized {
     type Token: Token<Self>;
     type Slice: Slice<Self>;
@@ -71,4 +104,44 @@ pub trait Input: Sized {
     /// Returns a context to identify the input spanning from `mark` until but
     /// excluding the current position.
     fn context(&mut self, _mark: Self::Marker) -> Self::Context;
+
+    /// Signals that `marker`, emitted by [`Input::mark()`], will no longer be
+    /// passed to [`Input::context()`] or [`Rewind::rewind_to()`]. Inputs that
+    /// retain consumed tokens for as long as a marker may need them, such as
+    /// [`IterInput`](crate::input::IterInput), use this to release them.
+    /// Parsers generated by `#[parser]` unmark their marker on exit. Does
+    /// nothing by default.
+    fn unmark(&mut self, _marker: Self::Marker) { }
+
+    /// Returns a measure of how far into the input the current position is,
+    /// if the input can report one. The measure only needs to grow as input
+    /// is consumed; it is used to compare positions when tracking the furthest
+    /// failure, see [`Options::track_furthest`](crate::input::Options), and to
+    /// key memoized results. It need not be an offset, as it isn't for `&str`,
+    /// so it is only meaningful relative to other progress values of the same
+    /// input. Returns `None`, disabling such tracking, by default.
+    fn progress(&mut self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the I/O error, if any, that kept the input from producing more
+    /// tokens. Inputs that don't perform I/O never return an error.
+    fn io_error(&mut self) -> Option<Arc<io::Error>> {
+        None
+    }
+
+    /// Returns a description of the malformed data, if any, at which the input
+    /// stopped producing tokens, such as an invalid encoding in a
+    /// [`Decoded`](crate::input::Decoded) input. Parsers that fail there report
+    /// it as an [`Expected::Other`](crate::error::Expected::Other). Returns
+    /// `None` by default.
+    fn invalid(&mut self) -> Option<&'static str> {
+        None
+    }
+
+    /// Returns `false` if more tokens may be appended to the input later, that
+    /// is, if running out of tokens doesn't mean the input has ended.
+    fn is_complete(&mut self) -> bool {
+        true
+    }
 }
@@ -0,0 +1,255 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::iter::Fuse;
+
+use crate::input::{Input, Rewind, Show, ParserInfo, Extent};
+
+/// An input over the items of any [`Iterator`], such as a hand-written lexer
+/// or the `char`s of a stream, pulled from the iterator on demand.
+///
+/// Items are buffered as far ahead as lookahead requires. Consumed items are
+/// retained only while a live marker may rewind to them or request a context
+/// spanning them: once every marker at or before an item has been released
+/// via [`Input::unmark()`], which parsers do on exit, the item is dropped the
+/// next time the buffer is refilled. Memory use is thus bounded by the span of
+/// the longest running parser plus its lookahead, not by the length of the
+/// stream. Rewinding to a released marker panics.
+///
+/// Markers are absolute item offsets. Slices, `Many`s, and contexts are
+/// [`Extent`]s of owned `Vec`s.
+///
+/// # Example
+///
+/// ```rust
+/// use pear::input::{Pear, IterInput};
+/// use pear::parsers::*;
+///
+/// let mut input: Pear<IterInput<_>> = Pear::new("hello, world".chars());
+/// let word = take_while(&mut input, |c| c.is_alphabetic()).unwrap();
+/// assert_eq!(word.values.iter().collect::<String>(), "hello");
+/// assert_eq!(eat(&mut input, ',').unwrap(), ',');
+/// ```
+#[derive(Debug)]
+pub struct IterInput<It: Iterator> {
+    iter: Fuse<It>,
+    // Buffered items. `buffer[0]` is at absolute offset `base`.
+    buffer: VecDeque<It::Item>,
+    base: usize,
+    // The absolute offset of the current position.
+    pos: usize,
+    // The number of live markers at each offset.
+    markers: BTreeMap<usize, usize>,
+}
+
+impl<It: Iterator> From<It> for IterInput<It> {
+    fn from(iter: It) -> Self {
+        IterInput::new(iter)
+    }
+}
+
+impl<It: Iterator> IterInput<It> {
+    /// Creates a new input over the items of `iter`.
+    pub fn new(iter: It) -> Self {
+        IterInput {
+            iter: iter.fuse(),
+            buffer: VecDeque::new(),
+            base: 0,
+            pos: 0,
+            markers: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the absolute offset of the current position.
+    pub fn offset(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the absolute offset of the oldest item that can be rewound to.
+    pub fn window_start(&self) -> usize {
+        self.base
+    }
+
+    /// Returns the number of items currently buffered, including those that
+    /// have been consumed but are retained for live markers.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline(always)]
+    fn available(&self) -> usize {
+        self.buffer.len() - (self.pos - self.base)
+    }
+
+    #[inline(always)]
+    fn get(&self, offset: usize) -> Option<&It::Item> {
+        self.buffer.get(offset - self.base)
+    }
+
+    // Drops items before both the current position and every live marker.
+    fn compact(&mut self) {
+        let oldest = self.markers.keys().next().map_or(self.pos, |&m| m.min(self.pos));
+        let droppable = oldest.saturating_sub(self.base);
+        if droppable > 0 {
+            self.buffer.drain(..droppable);
+            self.base += droppable;
+        }
+    }
+
+    // Tries to buffer at least `n` items past the current position. Returns
+    // `true` if that many items are available.
+    fn fill(&mut self, n: usize) -> bool {
+        if self.available() >= n {
+            return true;
+        }
+
+        self.compact();
+        while self.available() < n {
+            match self.iter.next() {
+                Some(item) => self.buffer.push_back(item),
+                None => return false,
+            }
+        }
+
+        true
+    }
+
+    fn extent(&self, start: usize, end: usize) -> Extent<Vec<It::Item>>
+        where It::Item: Clone
+    {
+        let from = start.max(self.base) - self.base;
+        let values = self.buffer.range(from..(end - self.base)).cloned().collect();
+        Extent { start, end, values }
+    }
+}
+
+impl<It: Iterator> Input for IterInput<It>
+    where It::Item: Show + PartialEq + Clone
+{
+    type Token = It::Item;
+    type Slice = Extent<Vec<It::Item>>;
+    type Many = Extent<Vec<It::Item>>;
+
+    type Marker = usize;
+    type Context = Extent<Vec<It::Item>>;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        match self.fill(1) {
+            true => self.get(self.pos).cloned(),
+            false => None,
+        }
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        match self.fill(n) {
+            true => Some(self.extent(self.pos, self.pos + n)),
+            false => None,
+        }
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.fill(1) && self.get(self.pos).map(&mut cond).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        if self.peek(&mut cond) {
+            self.pos += 1;
+            return self.get(self.pos - 1).cloned();
+        }
+
+        None
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.pos += n;
+            return Some(slice);
+        }
+
+        None
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let start = self.pos;
+        let mut values = vec![];
+        while let Some(token) = self.eat(&mut cond) {
+            values.push(token);
+        }
+
+        Extent { start, end: self.pos, values }
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let start = self.pos;
+        while self.peek(&mut cond) {
+            self.pos += 1;
+        }
+
+        self.pos - start
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.fill(n)
+    }
+
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        *self.markers.entry(self.pos).or_insert(0) += 1;
+        self.pos
+    }
+
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        self.extent(std::cmp::min(mark, self.pos), self.pos)
+    }
+
+    fn unmark(&mut self, marker: Self::Marker) {
+        if let Some(count) = self.markers.get_mut(&marker) {
+            *count -= 1;
+            if *count == 0 {
+                self.markers.remove(&marker);
+            }
+        }
+    }
+
+    fn progress(&mut self) -> Option<usize> {
+        Some(self.pos)
+    }
+}
+
+impl<It: Iterator> Rewind for IterInput<It>
+    where It::Item: Show + PartialEq + Clone
+{
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        assert!(marker >= self.base, "rewind to offset {} outside of retained window \
+            starting at {}", marker, self.base);
+
+        self.pos = marker;
+    }
+}
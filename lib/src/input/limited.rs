@@ -0,0 +1,196 @@
+use std::io;
+use std::sync::Arc;
+
+use crate::input::{Input, Rewind, ParserInfo};
+
+const INFO: ParserInfo = ParserInfo::new("limited", true);
+
+/// Returns the current position of `input`, releasing the marker used to
+/// find it.
+fn position<I: Input<Marker = usize>>(input: &mut I) -> usize {
+    let marker = input.mark(&INFO);
+    input.unmark(marker);
+    marker
+}
+
+/// An input that exposes a window of the next `n` units of a borrowed input
+/// `I` as if it were the entire input.
+///
+/// The window is measured in `I`'s markers, which for all of the inputs in
+/// this crate are offsets in the same units as slice lengths: bytes for
+/// [`Text`](crate::input::Text) and [`Bytes`](crate::input::Bytes), items for
+/// a [`Cursor`](crate::input::Cursor) over a slice. Tokens that straddle the
+/// end of the window are treated as being outside of it. Markers and contexts
+/// are `I`'s own, so positions in errors remain absolute.
+///
+/// A `Limited` input is created by the
+/// [`limit()`](crate::combinators::limit()) and
+/// [`limit_skip()`](crate::combinators::limit_skip()) combinators.
+#[derive(Debug)]
+pub struct Limited<'a, I> {
+    input: &'a mut I,
+    end: usize,
+}
+
+impl<'a, I: Input<Marker = usize> + Rewind> Limited<'a, I> {
+    /// Limits `input` to the next `n` units.
+    pub fn new(input: &'a mut I, n: usize) -> Self {
+        let end = position(input) + n;
+        Limited { input, end }
+    }
+
+    /// Returns the marker at which the window ends.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the number of units remaining in the window.
+    pub fn remaining(&mut self) -> usize {
+        self.end.saturating_sub(position(self.input))
+    }
+
+    /// Runs `f` on the underlying input, undoing its effect and returning
+    /// `None` if it moves the input past the end of the window.
+    fn within<T, F>(&mut self, f: F) -> Option<T>
+        where F: FnOnce(&mut I) -> Option<T>
+    {
+        let start = self.input.mark(&INFO);
+        let value = f(self.input).filter(|_| position(self.input) <= self.end);
+        if value.is_none() {
+            self.input.rewind_to(start);
+        }
+
+        self.input.unmark(start);
+        value
+    }
+
+    /// Returns the number of tokens, starting at the current one, that satisfy
+    /// `cond` and lie entirely within the window.
+    fn fitting<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&I::Token) -> bool
+    {
+        let start = self.input.mark(&INFO);
+        let mut count = 0;
+        while self.within(|input| input.eat(&mut cond)).is_some() {
+            count += 1;
+        }
+
+        self.input.rewind_to(start);
+        self.input.unmark(start);
+        count
+    }
+}
+
+impl<I: Input<Marker = usize> + Rewind> Input for Limited<'_, I> {
+    type Token = I::Token;
+    type Slice = I::Slice;
+    type Many = I::Many;
+
+    type Marker = I::Marker;
+    type Context = I::Context;
+
+    fn token(&mut self) -> Option<Self::Token> {
+        let start = self.input.mark(&INFO);
+        let token = self.within(|input| input.eat(|_| true));
+        self.input.rewind_to(start);
+        self.input.unmark(start);
+        token
+    }
+
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        match n <= self.remaining() {
+            true => self.input.slice(n),
+            false => None,
+        }
+    }
+
+    fn has(&mut self, n: usize) -> bool {
+        n <= self.remaining() && self.input.has(n)
+    }
+
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    fn eat<F>(&mut self, cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.within(|input| input.eat(cond))
+    }
+
+    fn eat_slice<F>(&mut self, n: usize, cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        match n <= self.remaining() {
+            true => self.input.eat_slice(n, cond),
+            false => None,
+        }
+    }
+
+    fn take<F>(&mut self, cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let n = self.fitting(cond);
+        let mut taken = 0;
+        self.input.take(|_| { taken += 1; taken <= n })
+    }
+
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let n = self.fitting(cond);
+        let mut skipped = 0;
+        self.input.skip(|_| { skipped += 1; skipped <= n })
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, info: &ParserInfo) -> Self::Marker {
+        self.input.mark(info)
+    }
+
+    #[inline(always)]
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        self.input.context(mark)
+    }
+
+    #[inline(always)]
+    fn unmark(&mut self, marker: Self::Marker) {
+        self.input.unmark(marker)
+    }
+
+    #[inline(always)]
+    fn progress(&mut self) -> Option<usize> {
+        self.input.progress()
+    }
+
+    #[inline(always)]
+    fn io_error(&mut self) -> Option<Arc<io::Error>> {
+        self.input.io_error()
+    }
+
+    #[inline(always)]
+    fn invalid(&mut self) -> Option<&'static str> {
+        self.input.invalid()
+    }
+
+    /// The window is complete if all of it is available. Otherwise, it's as
+    /// complete as the underlying input.
+    fn is_complete(&mut self) -> bool {
+        let remaining = self.remaining();
+        self.input.has(remaining) || self.input.is_complete()
+    }
+}
+
+impl<I: Input<Marker = usize> + Rewind> Rewind for Limited<'_, I> {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.input.rewind_to(marker)
+    }
+}
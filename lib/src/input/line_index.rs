@@ -0,0 +1,35 @@
+/// A table of the offsets at which each line of a source begins, used to
+/// resolve offsets into lines and columns in `O(log n)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LineIndex {
+    // `starts[i]` is the offset of the first byte of line `i + 1`.
+    starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(source: &[u8]) -> LineIndex {
+        LineIndex::from_chunks(std::iter::once(source))
+    }
+
+    /// Builds the index for the concatenation of `chunks`.
+    pub fn from_chunks<'a, C>(chunks: C) -> LineIndex
+        where C: IntoIterator<Item = &'a [u8]>
+    {
+        let newlines = chunks.into_iter().flatten().enumerate().filter(|&(_, &b)| b == b'\n');
+        let starts = std::iter::once(0).chain(newlines.map(|(i, _)| i + 1)).collect();
+        LineIndex { starts }
+    }
+
+    /// Returns the 1-indexed line and byte offset of the start of that line
+    /// for the byte at `offset`.
+    pub fn line(&self, offset: usize) -> (usize, usize) {
+        let line = self.starts.partition_point(|&start| start <= offset);
+        (line, self.starts[line - 1])
+    }
+
+    /// Returns the 1-indexed line and column, counted in bytes, of `offset`.
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let (line, start) = self.line(offset);
+        (line, offset - start + 1)
+    }
+}
@@ -0,0 +1,73 @@
+use std::any::Any;
+use std::collections::HashMap;
+
+use crate::input::Input;
+use crate::error::ParseError;
+
+/// Counts of lookups in the memo table of a parser memoized with
+/// `#[parser(memo)]`, as returned by [`Pear::memo_stats()`].
+///
+/// [`Pear::memo_stats()`]: crate::input::Pear::memo_stats()
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct MemoStats {
+    /// The number of times a memoized result was replayed.
+    pub hits: usize,
+    /// The number of times the parser ran and its result was memoized.
+    pub misses: usize,
+}
+
+impl MemoStats {
+    /// Returns the fraction of lookups that were hits, or `0.0` if there were
+    /// no lookups.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+/// The outcome of a memoized parser that started at some position.
+pub(crate) struct Entry<I: Input, D> {
+    /// Where the input was left when the parser returned.
+    pub end: I::Marker,
+    /// Whether the parser committed to the enclosing alternative.
+    pub cut: bool,
+    /// Whether errors were emitted when the parser ran.
+    pub emitted: bool,
+    /// The parser's output, downcast on replay, or its error.
+    pub result: Result<Box<dyn Any>, ParseError<I::Context, D>>,
+}
+
+/// A memoized parser that is running.
+pub(crate) struct Frame<I: Input> {
+    pub name: &'static str,
+    pub progress: Option<usize>,
+    /// Where the parser began, to which it returns to grow a seed.
+    pub start: I::Marker,
+    /// The `(emit_error, cut)` state when the parser began.
+    pub state: (bool, bool),
+    /// Whether the parser was called again at the same position, that is,
+    /// left-recursively.
+    pub recursive: bool,
+    /// Whether the parser ran within a left-recursive call to a parser that
+    /// began before it at the same position, in which case its result
+    /// depends on that parser's seed and isn't memoized.
+    pub tainted: bool,
+    /// The progress made by the longest seed grown so far.
+    pub grown: Option<usize>,
+}
+
+/// The memo table owned by a [`Pear`](crate::input::Pear), keyed by parser
+/// and [`Input::progress()`].
+pub(crate) struct Memo<I: Input, D> {
+    pub entries: HashMap<(&'static str, usize), Entry<I, D>>,
+    pub stats: HashMap<&'static str, MemoStats>,
+    pub active: Vec<Frame<I>>,
+}
+
+impl<I: Input, D> Default for Memo<I, D> {
+    fn default() -> Self {
+        Memo { entries: HashMap::new(), stats: HashMap::new(), active: vec![] }
+    }
+}
@@ -1,16 +1,45 @@
+#[allow(clippy::module_inception)]
 mod input;
 mod length;
 mod string;
 mod cursor;
 mod text;
-mod text_file;
+mod bytes;
+mod bits;
+mod line_index;
+mod reader;
+mod partial;
+mod source_map;
+mod tokens;
+mod trivia;
+mod limited;
+mod shared;
+mod chunks;
+mod iter;
+mod decode;
+mod case;
 mod show;
+mod memo;
 mod pear;
 
-pub use self::pear::{Pear, Debugger, Options};
-pub use input::{Input, Rewind, Token, Slice, ParserInfo};
+pub use self::pear::{Pear, Debugger, Options, Furthest};
+pub use memo::MemoStats;
+pub use input::{Input, Rewind, Seek, Token, Slice, ParserInfo};
 pub use cursor::{Cursor, Extent};
-pub use text::{Text, Span};
+pub use text::{Text, Span, ColumnUnit};
+pub use bytes::{Bytes, ByteSpan};
+pub use bits::{Bits, BitSlice, BitSpan, BitOffset};
+pub use reader::{ReaderInput, DEFAULT_WINDOW};
+pub use partial::Partial;
+pub use source_map::{SourceMap, SourceFile, FileId};
+pub use tokens::{Tokens, Spanned};
+pub use trivia::{Trivia, Skip, Whitespace};
+pub use limited::Limited;
+pub use shared::{Shared, SharedSlice, SharedSpan, Buffer};
+pub use chunks::{Chunks, Chunk};
+pub use iter::IterInput;
+pub use decode::{Decoded, Decoder, Encoded, Percent, Base64, Utf16};
+pub use case::IgnoreCase;
 pub use length::Length;
 pub use show::Show;
 
@@ -19,6 +48,3 @@ use crate::error;
 pub type Expected<I> = error::Expected<<I as Input>::Token, <I as Input>::Slice>;
 pub type ParseError<I> = error::ParseError<<I as Input>::Context, Expected<I>>;
 pub type Result<T, I> = std::result::Result<T, ParseError<I>>;
-
-// TODO: Implement new inputs: `Bytes` (akin to `Text`), `Cursor` but for
-// files/anything `Read`.
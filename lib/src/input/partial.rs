@@ -0,0 +1,163 @@
+use std::io;
+use std::sync::Arc;
+
+use crate::input::{Input, Rewind, ParserInfo};
+
+/// An input that wraps `I`, marking it as a prefix of a larger input.
+///
+/// Running out of tokens in a `Partial` input doesn't mean the input is over:
+/// more tokens may arrive later. Parsers that need more tokens than are
+/// available thus fail with
+/// [`Expected::Incomplete`](crate::error::Expected::Incomplete) carrying the
+/// minimum number of additional tokens (bytes for byte inputs) needed instead
+/// of reporting an unexpected EOF. Callers can then append more data to the
+/// underlying buffer and parse again from the last point they committed to,
+/// such as the end of the last fully parsed frame.
+///
+/// Because more input may always arrive, [`eof()`](crate::parsers::eof) never
+/// succeeds on a `Partial` input. Call parsers directly instead of via
+/// [`parse!`](crate::macros::parse), which checks for EOF.
+///
+/// ```rust
+/// use pear::input::{Pear, Cursor, Partial};
+/// use pear::error::Expected;
+/// use pear::parsers::eat_slice;
+///
+/// let mut input = Pear::from(Partial::new(Cursor::from(&b"HTTP/1"[..])));
+/// let result = eat_slice(&mut input, b"HTTP/1.1");
+/// assert!(matches!(result.unwrap_err().error, Expected::Incomplete(2)));
+/// ```
+#[derive(Debug)]
+pub struct Partial<I> {
+    input: I,
+}
+
+impl<I: Input> Partial<I> {
+    /// Wraps `input`, marking it as incomplete.
+    pub fn new(input: I) -> Self {
+        Partial { input }
+    }
+
+    /// Returns a reference to the wrapped input.
+    pub fn get_ref(&self) -> &I {
+        &self.input
+    }
+
+    /// Consumes `self`, returning the wrapped input.
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I: Input> From<I> for Partial<I> {
+    fn from(input: I) -> Self {
+        Partial::new(input)
+    }
+}
+
+impl<I: Input> Input for Partial<I> {
+    type Token = I::Token;
+    type Slice = I::Slice;
+    type Many = I::Many;
+
+    type Marker = I::Marker;
+    type Context = I::Context;
+
+    #[inline(always)]
+    fn token(&mut self) -> Option<Self::Token> {
+        self.input.token()
+    }
+
+    #[inline(always)]
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.input.slice(n)
+    }
+
+    #[inline(always)]
+    fn has(&mut self, n: usize) -> bool {
+        self.input.has(n)
+    }
+
+    #[inline(always)]
+    fn peek<F>(&mut self, cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.input.peek(cond)
+    }
+
+    #[inline(always)]
+    fn peek_slice<F>(&mut self, n: usize, cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.input.peek_slice(n, cond)
+    }
+
+    #[inline(always)]
+    fn eat<F>(&mut self, cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.input.eat(cond)
+    }
+
+    #[inline(always)]
+    fn eat_slice<F>(&mut self, n: usize, cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.input.eat_slice(n, cond)
+    }
+
+    #[inline(always)]
+    fn take<F>(&mut self, cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.input.take(cond)
+    }
+
+    #[inline(always)]
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.input.skip(cond)
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, info: &ParserInfo) -> Self::Marker {
+        self.input.mark(info)
+    }
+
+    #[inline(always)]
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        self.input.context(mark)
+    }
+
+    #[inline(always)]
+    fn unmark(&mut self, marker: Self::Marker) {
+        self.input.unmark(marker)
+    }
+
+    #[inline(always)]
+    fn progress(&mut self) -> Option<usize> {
+        self.input.progress()
+    }
+
+    #[inline(always)]
+    fn io_error(&mut self) -> Option<Arc<io::Error>> {
+        self.input.io_error()
+    }
+
+    #[inline(always)]
+    fn invalid(&mut self) -> Option<&'static str> {
+        self.input.invalid()
+    }
+
+    #[inline(always)]
+    fn is_complete(&mut self) -> bool {
+        false
+    }
+}
+
+impl<I: Input + Rewind> Rewind for Partial<I> {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.input.rewind_to(marker)
+    }
+}
@@ -1,6 +1,14 @@
 use std::fmt;
+use std::io;
+use std::any::Any;
+use std::sync::Arc;
+use std::collections::HashMap;
 
-use crate::input::{Input, Rewind, ParserInfo};
+use inlinable_string::InlinableString;
+
+use crate::input::{Input, Rewind, Seek, ParserInfo, Expected, Result, Limited};
+use crate::input::memo::{Memo, MemoStats, Entry, Frame};
+use crate::error::{self, ParseError};
 
 pub trait Debugger<I: Input> {
     fn on_entry(&mut self, info: &ParserInfo);
@@ -10,6 +18,13 @@ pub trait Debugger<I: Input> {
 pub struct Options<I> {
     pub stacked_context: bool,
     pub debugger: Option<Box<dyn Debugger<I>>>,
+    /// Whether to track the furthest position at which any parser failed,
+    /// even one whose error was discarded, such as an alternative in a
+    /// `switch!`, along with everything that was expected there. An error
+    /// at or before that position then reports the failure there as
+    /// [`Expected::OneOf`](crate::error::Expected::OneOf). Requires
+    /// [`Input::progress()`]. Disabled by default.
+    pub track_furthest: bool,
 }
 
 impl<I> fmt::Debug for Options<I> {
@@ -17,6 +32,7 @@ impl<I> fmt::Debug for Options<I> {
         f.debug_struct("Options")
             .field("stacked_context", &self.stacked_context)
             .field("debugger", &self.debugger.is_some())
+            .field("track_furthest", &self.track_furthest)
             .finish()
     }
 }
@@ -27,6 +43,7 @@ impl<I: Input> Default for Options<I> {
         Options {
             stacked_context: true,
             debugger: Some(Box::<crate::debug::TreeDebugger>::default()),
+            track_furthest: false,
         }
     }
 
@@ -35,17 +52,88 @@ impl<I: Input> Default for Options<I> {
         Options {
             stacked_context: false,
             debugger: None,
+            track_furthest: false,
         }
     }
 }
 
-#[derive(Debug)]
-pub struct Pear<I: Input> {
+/// The furthest failure recorded when
+/// [`Options::track_furthest`](Options#structfield.track_furthest) is enabled.
+pub struct Furthest<I: Input> {
+    /// The [`Input::progress()`] at the failure: comparable to other progress
+    /// values of the same input, but not necessarily an offset.
+    pub progress: usize,
+    /// The context at the failure.
+    pub context: I::Context,
+    /// Labels for each distinct token or slice expected at the failure.
+    pub expected: Vec<InlinableString>,
+    /// The token found at the failure, if any.
+    pub found: Option<I::Token>,
+}
+
+impl<I: Input> Furthest<I> {
+    /// Converts `self` to the failure of an input over the same tokens.
+    fn cast<J>(self) -> Furthest<J>
+        where J: Input<Token = I::Token, Context = I::Context>
+    {
+        let Furthest { progress, context, expected, found } = self;
+        Furthest { progress, context, expected, found }
+    }
+}
+
+impl<I: Input> fmt::Debug for Furthest<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Furthest")
+            .field("progress", &self.progress)
+            .field("expected", &self.expected)
+            .finish()
+    }
+}
+
+/// An [`Input`] being parsed, with the state parsers share while parsing it.
+///
+/// Errors recovered from with [`recover()`](crate::combinators::recover()) and
+/// friends are recorded as diagnostics of type `D`, which must be the error
+/// type of the recovering parsers. Use [`Pear::with_error_type()`] to create a
+/// `Pear` whose diagnostics are of a custom error type.
+pub struct Pear<I: Input, D = Expected<I>> {
     pub input: I,
     #[doc(hidden)]
     pub emit_error: bool,
     #[doc(hidden)]
-    pub options: Options<I>
+    pub options: Options<I>,
+    furthest: Option<Furthest<I>>,
+    diagnostics: Vec<ParseError<I::Context, D>>,
+    // Whether a parser failed because the input was cut short or ended at
+    // malformed data, which combinators can't recover from.
+    pub(crate) exhausted: bool,
+    // The last failure `fail()` reported as a mismatch, which a labeled parser
+    // that consumed nothing may replace with its label.
+    mismatch: Option<Mismatch>,
+    cut: bool,
+    memo: Memo<I, D>,
+}
+
+#[derive(Debug)]
+struct Mismatch {
+    progress: usize,
+    parser: &'static str,
+    // The labels expected at `progress` when tracking the furthest failure.
+    expected: Vec<InlinableString>,
+}
+
+impl<I: Input + fmt::Debug, D> fmt::Debug for Pear<I, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pear")
+            .field("input", &self.input)
+            .field("emit_error", &self.emit_error)
+            .field("options", &self.options)
+            .field("furthest", &self.furthest)
+            .field("diagnostics", &self.diagnostics.len())
+            .field("cut", &self.cut)
+            .field("memo", &self.memo.entries.len())
+            .finish()
+    }
 }
 
 impl<I: Input> Pear<I> {
@@ -54,26 +142,485 @@ impl<I: Input> Pear<I> {
     }
 }
 
+impl<I: Input, D> Pear<I, D> {
+    /// Creates a `Pear` over `input` whose diagnostics are of type `D`, the
+    /// error type of the parsers that recover from errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pear::input::{Pear, Text};
+    /// use pear::error::Expected;
+    ///
+    /// #[derive(Debug)]
+    /// enum Error<'a> {
+    ///     Expected(Expected<char, &'a str>),
+    /// }
+    ///
+    /// let input: Pear<Text<'_>, Error<'_>> = Pear::with_error_type(Text::from("abc"));
+    /// assert!(input.diagnostics().is_empty());
+    /// ```
+    pub fn with_error_type(input: I) -> Self {
+        Pear {
+            input,
+            emit_error: true,
+            options: Options::default(),
+            furthest: None,
+            diagnostics: vec![],
+            exhausted: false,
+            mismatch: None,
+            cut: false,
+            memo: Memo::default(),
+        }
+    }
+
+    /// Returns the furthest failure recorded so far, if
+    /// [`Options::track_furthest`](Options#structfield.track_furthest) is
+    /// enabled and a failure hasn't since been reported.
+    pub fn furthest(&self) -> Option<&Furthest<I>> {
+        self.furthest.as_ref()
+    }
+
+    /// Returns the errors recorded so far by
+    /// [`recover()`](crate::combinators::recover()) and friends, in the order
+    /// they occurred.
+    pub fn diagnostics(&self) -> &[ParseError<I::Context, D>] {
+        &self.diagnostics
+    }
+
+    /// Records `error` as a diagnostic without failing.
+    pub fn push_diagnostic(&mut self, error: ParseError<I::Context, D>) {
+        self.diagnostics.push(error);
+    }
+
+    /// Removes and returns all of the diagnostics recorded so far.
+    pub fn take_diagnostics(&mut self) -> Vec<ParseError<I::Context, D>> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// Commits to the alternative being parsed: if parsing fails from here on,
+    /// enclosing `switch!`s, [`try_collect()`], and [`trailing_series()`]
+    /// propagate the error instead of trying another alternative, and [`ok()`]
+    /// and [`succeeds()`] leave the cut in place for the next enclosing
+    /// alternative. Errors are emitted from here on, even if the alternative
+    /// began with errors discarded.
+    ///
+    /// This is what `cut!()` and `#[parser(commit)]` do.
+    ///
+    /// [`try_collect()`]: crate::combinators::try_collect()
+    /// [`trailing_series()`]: crate::combinators::trailing_series()
+    /// [`ok()`]: crate::combinators::ok()
+    /// [`succeeds()`]: crate::combinators::succeeds()
+    pub fn cut(&mut self) {
+        self.cut = true;
+        self.emit_error = true;
+    }
+
+    /// Returns `true` if the alternative being parsed has been committed to
+    /// with [`Pear::cut()`].
+    pub fn is_cut(&self) -> bool {
+        self.cut
+    }
+
+    /// Begins an alternative: discards errors and clears the cut until the
+    /// matching [`Pear::exit_alternative()`], returning the state to restore.
+    #[doc(hidden)]
+    pub fn enter_alternative(&mut self) -> (bool, bool) {
+        let saved = (self.emit_error, self.cut);
+        self.emit_error = false;
+        self.cut = false;
+        saved
+    }
+
+    /// Ends an alternative begun with [`Pear::enter_alternative()`], returning
+    /// `true` if it `failed` after a cut, in which case the cut is left in
+    /// place and the failure must be propagated.
+    #[doc(hidden)]
+    pub fn exit_alternative(&mut self, saved: (bool, bool), failed: bool) -> bool {
+        let (emit_error, cut) = saved;
+        self.emit_error = emit_error;
+        if failed && self.cut {
+            return true;
+        }
+
+        self.cut = cut;
+        false
+    }
+
+    /// Returns the memo table statistics of each parser memoized with
+    /// `#[parser(memo)]` that has run, keyed by its module path and name.
+    pub fn memo_stats(&self) -> &HashMap<&'static str, MemoStats> {
+        &self.memo.stats
+    }
+
+    /// Removes all memoized results, keeping the statistics.
+    pub fn clear_memo(&mut self) {
+        self.memo.entries.clear();
+    }
+
+    /// Replays the result memoized for the parser `info`, identified by `name`,
+    /// at `progress`, if any. If the parser is already running at `progress`,
+    /// the call is left-recursive: the seed grown so far is replayed instead,
+    /// or, initially, the call fails.
+    #[doc(hidden)]
+    pub fn recall<O>(
+        &mut self,
+        info: &ParserInfo,
+        name: &'static str,
+        progress: Option<usize>,
+    ) -> Option<crate::result::Result<O, I::Context, D>>
+        where I: Rewind, O: Clone + 'static, D: Clone + From<Expected<I>>, I::Context: Clone
+    {
+        let progress = progress?;
+        let running = self.memo.active.iter()
+            .rposition(|f| f.name == name && f.progress == Some(progress));
+
+        if let Some(i) = running {
+            self.memo.active[i].recursive = true;
+            for frame in &mut self.memo.active[i + 1..] {
+                frame.tainted = true;
+            }
+
+            if let Some(seed) = self.replay(name, progress) {
+                return Some(seed);
+            }
+
+            let expected = match self.emit_error {
+                true => crate::iformat!(
+                    "no base case for left-recursive {}", info.display_name()).into(),
+                false => error::Expected::Elided,
+            };
+
+            let mark = self.input.mark(info);
+            let context = self.input.context(mark);
+            self.input.unmark(mark);
+            return Some(Err(ParseError::new(*info, expected, context).into()));
+        }
+
+        let result = self.replay(name, progress)?;
+        self.memo.stats.entry(name).or_default().hits += 1;
+        Some(result)
+    }
+
+    /// Begins running the memoized parser `info`, identified by `name`, at
+    /// `progress`. Must be followed by [`Pear::exit_memo()`].
+    #[doc(hidden)]
+    pub fn enter_memo(&mut self, info: &ParserInfo, name: &'static str, progress: Option<usize>) {
+        let start = self.input.mark(info);
+        self.memo.active.push(Frame {
+            name, progress, start,
+            state: (self.emit_error, self.cut),
+            recursive: false,
+            tainted: false,
+            grown: None,
+        });
+    }
+
+    /// Finishes running the memoized parser begun last with
+    /// [`Pear::enter_memo()`], which returned `result`, and returns what it
+    /// should return. If the parser was called left-recursively and `result`
+    /// progressed further than the seed grown so far, `result` becomes the
+    /// seed, the input returns to where the parser began, and `None` is
+    /// returned: the parser must be run again.
+    #[doc(hidden)]
+    pub fn exit_memo<O>(
+        &mut self,
+        mut result: crate::result::Result<O, I::Context, D>,
+    ) -> Option<crate::result::Result<O, I::Context, D>>
+        where I: Rewind, O: Clone + 'static, D: Clone, I::Context: Clone
+    {
+        let frame = self.memo.active.last_mut().expect("exit_memo() without enter_memo()");
+        if let (true, Some(progress)) = (frame.recursive, frame.progress) {
+            let (name, start, (emit_error, cut)) = (frame.name, frame.start, frame.state);
+            let end = self.input.progress();
+            if result.is_ok() && end > frame.grown {
+                frame.grown = end;
+                self.store(name, progress, cut, result);
+                self.input.rewind_to(start);
+                self.emit_error = emit_error;
+                self.cut = cut;
+                return None;
+            }
+
+            if frame.grown.is_some() {
+                if let Some(seed) = self.replay(name, progress) {
+                    result = seed;
+                }
+            }
+        }
+
+        let frame = self.memo.active.pop().expect("active frame");
+        if let Some(progress) = frame.progress {
+            match frame.tainted {
+                true => { self.memo.entries.remove(&(frame.name, progress)); }
+                false => {
+                    self.store(frame.name, progress, frame.state.1, result.clone());
+                    self.memo.stats.entry(frame.name).or_default().misses += 1;
+                }
+            }
+        }
+
+        self.input.unmark(frame.start);
+        Some(result)
+    }
+
+    /// Replays the result memoized for `name` at `progress`, moving to where
+    /// the input was left and recommitting if the parser committed. An error
+    /// memoized while errors were discarded isn't replayed while they're
+    /// emitted, nor is an output of a type other than `O`.
+    fn replay<O>(
+        &mut self,
+        name: &'static str,
+        progress: usize,
+    ) -> Option<crate::result::Result<O, I::Context, D>>
+        where I: Rewind, O: Clone + 'static, D: Clone, I::Context: Clone
+    {
+        let entry = self.memo.entries.get(&(name, progress))?;
+        let result = match entry.result {
+            Ok(ref output) => Ok(output.downcast_ref::<O>()?.clone()),
+            Err(_) if self.emit_error && !entry.emitted => return None,
+            Err(ref e) => Err(e.clone()),
+        };
+
+        let (end, cut) = (entry.end, entry.cut);
+        self.input.rewind_to(end);
+        if cut {
+            self.cut();
+        }
+
+        Some(result)
+    }
+
+    /// Memoizes `result` for `name` at `progress`, where the input is now, for
+    /// a parser that began while the cut was `cut`.
+    fn store<O: 'static>(
+        &mut self,
+        name: &'static str,
+        progress: usize,
+        cut: bool,
+        result: crate::result::Result<O, I::Context, D>,
+    ) {
+        let info = ParserInfo::new("memo", true);
+        let end = self.input.mark(&info);
+        self.input.unmark(end);
+        let (cut, emitted) = (self.cut && !cut, self.emit_error);
+        let result = result.map(|output| Box::new(output) as Box<dyn Any>);
+        self.memo.entries.insert((name, progress), Entry { end, cut, emitted, result });
+    }
+
+    /// Runs `f` against a window of the next `n` units of the input. The
+    /// window shares the options, cut, furthest failure, and diagnostics of
+    /// `self`.
+    pub(crate) fn limited<F, O>(&mut self, n: usize, f: F) -> O
+        where I: Input<Marker = usize> + Rewind,
+              F: for<'a> FnOnce(&mut Pear<Limited<'a, I>, D>) -> O
+    {
+        let mut window = Pear::with_error_type(Limited::new(&mut self.input, n));
+        window.emit_error = self.emit_error;
+        window.options = Options {
+            stacked_context: self.options.stacked_context,
+            debugger: None,
+            track_furthest: self.options.track_furthest,
+        };
+
+        window.cut = self.cut;
+        window.furthest = self.furthest.take().map(Furthest::cast);
+        window.mismatch = self.mismatch.take();
+        window.diagnostics = std::mem::take(&mut self.diagnostics);
+        let output = f(&mut window);
+        (self.emit_error, self.cut) = (window.emit_error, window.cut);
+        self.furthest = window.furthest.take().map(Furthest::cast);
+        self.mismatch = window.mismatch.take();
+        self.diagnostics = std::mem::take(&mut window.diagnostics);
+        self.exhausted |= window.exhausted;
+        output
+    }
+
+    /// Returns the error for a failure of the parser `info`, marked at `mark`,
+    /// that expected `expected` at the current position. If tracking, records
+    /// the failure, then reports the furthest failure instead if it is further
+    /// along or expected more at the same position.
+    pub(crate) fn fail<O>(
+        &mut self,
+        info: ParserInfo,
+        mark: I::Marker,
+        expected: Expected<I>,
+    ) -> Result<O, I> {
+        if self.options.track_furthest {
+            self.record(&info, &expected);
+        }
+
+        use error::Expected::{Token, Slice, Eof};
+        self.mismatch = match (&expected, self.input.progress()) {
+            (Token(..) | Slice(..) | Eof(_), Some(progress)) => {
+                let expected = match self.furthest {
+                    Some(ref f) if f.progress == progress => f.expected.clone(),
+                    _ => vec![],
+                };
+
+                Some(Mismatch { progress, parser: info.name, expected })
+            }
+            _ => None,
+        };
+
+        if !self.emit_error {
+            let context = self.input.context(mark);
+            return Err(ParseError::new(info, error::Expected::Elided, context));
+        }
+
+        if let (Some(_), Some(progress)) = (label(&expected), self.input.progress()) {
+            match self.furthest.take() {
+                Some(f) if f.progress > progress || f.expected.len() > 1 => {
+                    let expected = error::Expected::OneOf(f.expected, f.found);
+                    return Err(ParseError::new(info, expected, f.context));
+                }
+                furthest => self.furthest = furthest,
+            }
+        }
+
+        Err(ParseError::new(info, expected, self.input.context(mark)))
+    }
+
+    /// Returns the number of labels the furthest failure expects at
+    /// `progress`, which a labeled parser starting there keeps when relabeled.
+    #[doc(hidden)]
+    pub fn expecting(&self, progress: Option<usize>) -> usize {
+        match self.furthest {
+            Some(ref f) if Some(f.progress) == progress => f.expected.len(),
+            _ => 0,
+        }
+    }
+
+    /// Replaces `error`, the failure of the labeled parser `info` that began
+    /// at `progress`, with one expecting the parser's label if the parser
+    /// consumed no input and failed on a token, slice, or EOF mismatch. When
+    /// tracking, the label also replaces those recorded inside the parser, of
+    /// the furthest failure's first `known`, even if errors aren't emitted.
+    /// Other errors, and those from a failure tracked further along, are left
+    /// as they are.
+    #[doc(hidden)]
+    pub fn relabel<E: From<Expected<I>>>(
+        &mut self,
+        info: &ParserInfo,
+        progress: Option<usize>,
+        known: usize,
+        error: &mut ParseError<I::Context, E>,
+    ) {
+        let (label, progress) = match (info.label, progress) {
+            (Some(label), Some(progress)) => (label, progress),
+            _ => return,
+        };
+
+        if self.input.progress() != Some(progress) || self.input.io_error().is_some()
+            || self.input.invalid().is_some()
+        {
+            return;
+        }
+
+        let mut expected = match self.mismatch {
+            Some(ref mut m) if m.progress == progress && m.parser == error.info.parser.name => {
+                std::mem::take(&mut m.expected)
+            }
+            _ => return,
+        };
+
+        if let Some(ref f) = self.furthest {
+            if f.progress > progress {
+                return;
+            }
+        }
+
+        let found = self.input.token();
+        if found.is_none() && !self.input.is_complete() {
+            return;
+        }
+
+        expected.truncate(known);
+        if !expected.iter().any(|l| l == label) {
+            expected.push(label.into());
+        }
+
+        if self.options.track_furthest {
+            match self.furthest {
+                Some(ref f) if f.progress == progress && self.emit_error && expected.len() > 1 => {
+                    self.furthest = None;
+                }
+                Some(ref mut f) if f.progress == progress => f.expected = expected.clone(),
+                _ => {}
+            }
+        }
+
+        if self.emit_error {
+            let labels = match self.options.track_furthest {
+                true => expected.clone(),
+                false => vec![label.into()],
+            };
+
+            error.error = E::from(error::Expected::OneOf(labels, found));
+        }
+
+        error.info.parser = *info;
+        self.mismatch = Some(Mismatch { progress, parser: info.name, expected });
+    }
+
+    fn record(&mut self, info: &ParserInfo, expected: &Expected<I>) {
+        let (label, progress) = match (label(expected), self.input.progress()) {
+            (Some(label), Some(progress)) => (label, progress),
+            _ => return,
+        };
+
+        match self.furthest {
+            Some(ref f) if f.progress > progress => return,
+            Some(ref mut f) if f.progress == progress => {
+                if !f.expected.contains(&label) {
+                    f.expected.push(label);
+                }
+
+                return;
+            }
+            _ => {}
+        }
+
+        let mark = self.input.mark(info);
+        let context = self.input.context(mark);
+        self.input.unmark(mark);
+        let found = self.input.token();
+        self.furthest = Some(Furthest { progress, context, expected: vec![label], found });
+    }
+}
+
+/// Returns the label under which `expected` is merged into a
+/// [`Furthest`] failure, if it can be.
+fn label<T, S>(expected: &error::Expected<T, S>) -> Option<InlinableString> {
+    use error::Expected::*;
+
+    match expected {
+        Token(Some(label), _) | Slice(Some(label), _) => Some(label.clone()),
+        Eof(_) => Some("EOF".into()),
+        _ => None,
+    }
+}
+
 impl<I: Input> From<I> for Pear<I> {
     fn from(input: I) -> Pear<I> {
-        Pear { input, emit_error: true, options: Options::default() }
+        Pear::with_error_type(input)
     }
 }
 
-impl<I: Input> std::ops::Deref for Pear<I> {
+impl<I: Input, D> std::ops::Deref for Pear<I, D> {
     type Target = I;
     fn deref(&self) -> &Self::Target {
         &self.input
     }
 }
 
-impl<I: Input> std::ops::DerefMut for Pear<I> {
+impl<I: Input, D> std::ops::DerefMut for Pear<I, D> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.input
     }
 }
 
-impl<I: Input> Input for Pear<I> {
+impl<I: Input, D> Input for Pear<I, D> {
     type Token = I::Token;
     type Slice = I::Slice;
     type Many = I::Many;
@@ -147,10 +694,41 @@ impl<I: Input> Input for Pear<I> {
     fn context(&mut self, mark: Self::Marker) -> Self::Context {
         self.input.context(mark)
     }
+
+    #[inline(always)]
+    fn unmark(&mut self, marker: Self::Marker) {
+        self.input.unmark(marker)
+    }
+
+    #[inline(always)]
+    fn progress(&mut self) -> Option<usize> {
+        self.input.progress()
+    }
+
+    #[inline(always)]
+    fn io_error(&mut self) -> Option<Arc<io::Error>> {
+        self.input.io_error()
+    }
+
+    #[inline(always)]
+    fn invalid(&mut self) -> Option<&'static str> {
+        self.input.invalid()
+    }
+
+    #[inline(always)]
+    fn is_complete(&mut self) -> bool {
+        self.input.is_complete()
+    }
 }
 
-impl<I: Input + Rewind> Rewind for Pear<I> {
+impl<I: Input + Rewind, D> Rewind for Pear<I, D> {
     fn rewind_to(&mut self, marker: Self::Marker) {
         self.input.rewind_to(marker)
     }
 }
+
+impl<I: Input + Seek, D> Seek for Pear<I, D> {
+    fn seek_to(&mut self, offset: usize) -> bool {
+        self.input.seek_to(offset)
+    }
+}
@@ -0,0 +1,269 @@
+use std::io::{self, Read};
+use std::sync::Arc;
+
+use crate::input::{Input, Rewind, ParserInfo, Extent};
+
+/// The default number of bytes behind the current position that are retained
+/// and can thus be rewound to.
+pub const DEFAULT_WINDOW: usize = 8 * 1024;
+
+/// The default number of bytes requested from the reader at a time.
+const CHUNK_SIZE: usize = 4 * 1024;
+
+/// An input that reads bytes on demand from any [`Read`].
+///
+/// Only a bounded window of already consumed bytes is retained: at least
+/// `window` bytes behind the current position are kept, and anything older may
+/// be discarded the next time the buffer is refilled, even if a marker still
+/// points to it. Memory use thus stays bounded however long the stream is, but
+/// `window` must cover the furthest any parser backtracks: rewinding to a
+/// marker that has left the window panics. Contexts report absolute byte
+/// offsets; their values hold at most the last `window` bytes before the
+/// current position.
+///
+/// An I/O error ends the input. Parsers that fail because no more data is
+/// available then report [`Expected::Io`](crate::error::Expected::Io) instead
+/// of an unexpected EOF.
+#[derive(Debug)]
+pub struct ReaderInput<R> {
+    reader: R,
+    // Buffered bytes. `buffer[0]` is at absolute offset `base`.
+    buffer: Vec<u8>,
+    base: usize,
+    // The absolute offset of the current position.
+    pos: usize,
+    window: usize,
+    eof: bool,
+    error: Option<Arc<io::Error>>,
+}
+
+impl<R: Read> From<R> for ReaderInput<R> {
+    fn from(reader: R) -> Self {
+        ReaderInput::new(reader)
+    }
+}
+
+impl<R: Read> ReaderInput<R> {
+    /// Creates a new input that retains [`DEFAULT_WINDOW`] bytes.
+    pub fn new(reader: R) -> Self {
+        ReaderInput::with_window(reader, DEFAULT_WINDOW)
+    }
+
+    /// Creates a new input that retains at least `window` bytes behind the
+    /// current position.
+    pub fn with_window(reader: R, window: usize) -> Self {
+        ReaderInput {
+            reader,
+            buffer: Vec::with_capacity(CHUNK_SIZE),
+            base: 0,
+            pos: 0,
+            window,
+            eof: false,
+            error: None,
+        }
+    }
+
+    /// Returns the absolute offset of the current position.
+    pub fn offset(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the absolute offset of the oldest byte that can be rewound to.
+    pub fn window_start(&self) -> usize {
+        self.base
+    }
+
+    /// Consumes `self`, returning the underlying reader. Buffered bytes that
+    /// were not consumed are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    #[inline(always)]
+    fn buffered(&self) -> &[u8] {
+        &self.buffer[(self.pos - self.base)..]
+    }
+
+    // Drops bytes more than `window` behind the current position, but only if
+    // that frees at least half of the buffer, so compaction is amortized.
+    fn compact(&mut self) {
+        let droppable = self.pos.saturating_sub(self.window).saturating_sub(self.base);
+        if droppable > 0 && droppable >= self.buffer.len() / 2 {
+            self.buffer.drain(..droppable);
+            self.base += droppable;
+        }
+    }
+
+    // Tries to buffer at least `n` bytes past the current position. Returns
+    // `true` if that many bytes are available.
+    fn fill(&mut self, n: usize) -> bool {
+        while self.buffered().len() < n {
+            if self.eof || self.error.is_some() {
+                return false;
+            }
+
+            self.compact();
+            let len = self.buffer.len();
+            let needed = n - self.buffered().len();
+            self.buffer.resize(len + std::cmp::max(needed, CHUNK_SIZE), 0);
+            let result = self.reader.read(&mut self.buffer[len..]);
+            self.buffer.truncate(len + *result.as_ref().unwrap_or(&0));
+            match result {
+                Ok(0) => self.eof = true,
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => self.error = Some(Arc::new(e)),
+            }
+        }
+
+        true
+    }
+}
+
+impl<R: Read> Input for ReaderInput<R> {
+    type Token = u8;
+    type Slice = Extent<Vec<u8>>;
+    type Many = Extent<Vec<u8>>;
+
+    type Marker = usize;
+    type Context = Extent<Vec<u8>>;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        match self.fill(1) {
+            true => Some(self.buffered()[0]),
+            false => None,
+        }
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        if !self.fill(n) {
+            return None;
+        }
+
+        let values = self.buffered()[..n].to_vec();
+        Some(Extent { start: self.pos, end: self.pos + n, values })
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let token = self.token()?;
+        if cond(&token) {
+            self.pos += 1;
+            return Some(token);
+        }
+
+        None
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.pos += n;
+            return Some(slice);
+        }
+
+        None
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let start = self.pos;
+        let mut values = vec![];
+        while self.fill(1) {
+            let buffered = self.buffered();
+            match buffered.iter().position(|b| !cond(b)) {
+                Some(taken) => {
+                    values.extend_from_slice(&buffered[..taken]);
+                    self.pos += taken;
+                    break;
+                }
+                None => {
+                    values.extend_from_slice(buffered);
+                    self.pos += buffered.len();
+                }
+            }
+        }
+
+        Extent { start, end: self.pos, values }
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let start = self.pos;
+        while self.fill(1) {
+            let buffered = self.buffered();
+            match buffered.iter().position(|b| !cond(b)) {
+                Some(skipped) => {
+                    self.pos += skipped;
+                    break;
+                }
+                None => self.pos += buffered.len(),
+            }
+        }
+
+        self.pos - start
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.fill(n)
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.pos
+    }
+
+    fn progress(&mut self) -> Option<usize> {
+        Some(self.pos)
+    }
+
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        let oldest = std::cmp::max(self.base, self.pos.saturating_sub(self.window));
+        let start = std::cmp::min(std::cmp::max(mark, oldest), self.pos);
+        let values = self.buffer[(start - self.base)..(self.pos - self.base)].to_vec();
+        Extent { start: mark, end: self.pos, values }
+    }
+
+    fn io_error(&mut self) -> Option<Arc<io::Error>> {
+        self.error.clone()
+    }
+}
+
+impl<R: Read> Rewind for ReaderInput<R> {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        assert!(marker >= self.base, "rewind to offset {} outside of retained window \
+            starting at {}", marker, self.base);
+
+        self.pos = marker;
+    }
+}
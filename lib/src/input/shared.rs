@@ -0,0 +1,483 @@
+use std::sync::OnceLock;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::input::{Input, Rewind, Seek, Show, ParserInfo, Length, Span, ByteSpan};
+use crate::input::line_index::LineIndex;
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for str {}
+    impl Sealed for [u8] {}
+}
+
+/// Trait implemented by the buffers a [`Shared`] input can parse: `str`, with
+/// `char` tokens, and `[u8]`, with `u8` tokens.
+pub trait Buffer: private::Sealed + PartialEq + 'static {
+    /// The type of the buffer's tokens.
+    type Token: Show + PartialEq + Copy;
+
+    #[doc(hidden)]
+    fn buffer_len(&self) -> usize;
+
+    #[doc(hidden)]
+    fn range(&self, range: Range<usize>) -> Option<&Self>;
+
+    #[doc(hidden)]
+    fn first_token(&self) -> Option<(Self::Token, usize)>;
+
+    #[doc(hidden)]
+    fn bytes(&self) -> &[u8];
+
+    #[doc(hidden)]
+    fn show(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    #[doc(hidden)]
+    fn show_span(span: &SharedSpan<Self>, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+impl Buffer for str {
+    type Token = char;
+
+    fn buffer_len(&self) -> usize {
+        self.len()
+    }
+
+    fn range(&self, range: Range<usize>) -> Option<&Self> {
+        self.get(range)
+    }
+
+    fn first_token(&self) -> Option<(char, usize)> {
+        self.chars().next().map(|c| (c, c.len_utf8()))
+    }
+
+    fn bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+
+    fn show(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Show::fmt(&self, f)
+    }
+
+    fn show_span(span: &SharedSpan<Self>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = Span {
+            start: span.start,
+            end: span.end,
+            cursor: span.cursor,
+            snippet: span.snippet.as_deref(),
+            columns: Default::default(),
+            file: None,
+        };
+
+        Show::fmt(&span, f)
+    }
+}
+
+impl Buffer for [u8] {
+    type Token = u8;
+
+    fn buffer_len(&self) -> usize {
+        self.len()
+    }
+
+    fn range(&self, range: Range<usize>) -> Option<&Self> {
+        self.get(range)
+    }
+
+    fn first_token(&self) -> Option<(u8, usize)> {
+        self.first().map(|&b| (b, 1))
+    }
+
+    fn bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn show(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Show::fmt(self, f)
+    }
+
+    fn show_span(span: &SharedSpan<Self>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = ByteSpan {
+            start: span.start,
+            end: span.end,
+            cursor: span.cursor,
+            snippet: span.snippet.as_deref(),
+        };
+
+        Show::fmt(&span, f)
+    }
+}
+
+/// A cheaply cloneable, reference-counted slice of a [`Shared`] input's
+/// buffer. Dereferences to `T`.
+#[derive(Clone)]
+pub struct SharedSlice<T: ?Sized + Buffer> {
+    source: Arc<T>,
+    start: usize,
+    end: usize,
+}
+
+impl<T: ?Sized + Buffer> SharedSlice<T> {
+    /// Returns the entire buffer the slice is a part of.
+    pub fn source(&self) -> &Arc<T> {
+        &self.source
+    }
+
+    /// Returns the range of the buffer the slice spans.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+impl<T: ?Sized + Buffer> std::ops::Deref for SharedSlice<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.source.range(self.start..self.end).expect("valid range")
+    }
+}
+
+impl<T: ?Sized + Buffer + fmt::Debug> fmt::Debug for SharedSlice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl fmt::Display for SharedSlice<str> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + Buffer> Show for SharedSlice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::show(self, f)
+    }
+}
+
+impl<T: ?Sized + Buffer> Length for SharedSlice<T> {
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+impl<T: ?Sized + Buffer> PartialEq for SharedSlice<T> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: ?Sized + Buffer + Eq> Eq for SharedSlice<T> { }
+
+impl<T: ?Sized + Buffer + std::hash::Hash> std::hash::Hash for SharedSlice<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl PartialEq<&str> for SharedSlice<str> {
+    fn eq(&self, other: &&str) -> bool {
+        &**self == *other
+    }
+}
+
+impl PartialEq<SharedSlice<str>> for &str {
+    fn eq(&self, other: &SharedSlice<str>) -> bool {
+        *self == &**other
+    }
+}
+
+impl PartialEq<&[u8]> for SharedSlice<[u8]> {
+    fn eq(&self, other: &&[u8]) -> bool {
+        &**self == *other
+    }
+}
+
+impl PartialEq<SharedSlice<[u8]>> for &[u8] {
+    fn eq(&self, other: &SharedSlice<[u8]>) -> bool {
+        *self == &**other
+    }
+}
+
+impl<const N: usize> PartialEq<&[u8; N]> for SharedSlice<[u8]> {
+    fn eq(&self, other: &&[u8; N]) -> bool {
+        **self == other[..]
+    }
+}
+
+impl<const N: usize> PartialEq<SharedSlice<[u8]>> for &[u8; N] {
+    fn eq(&self, other: &SharedSlice<[u8]>) -> bool {
+        self[..] == **other
+    }
+}
+
+/// The context of a [`Shared`] input. Like [`Span`] for `Shared<str>` and
+/// [`ByteSpan`] for `Shared<[u8]>`, but owned, so it is `'static`.
+#[derive(Clone)]
+pub struct SharedSpan<T: ?Sized + Buffer> {
+    /// Start line/column/offset.
+    pub start: (usize, usize, usize),
+    /// End line/column/offset.
+    pub end: (usize, usize, usize),
+    /// Where the parser was pointing.
+    pub cursor: Option<T::Token>,
+    /// Snippet between start and end.
+    pub snippet: Option<SharedSlice<T>>,
+}
+
+impl<T: ?Sized + Buffer + fmt::Debug> fmt::Debug for SharedSpan<T>
+    where T::Token: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedSpan")
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("cursor", &self.cursor)
+            .field("snippet", &self.snippet)
+            .finish()
+    }
+}
+
+impl<T: ?Sized + Buffer> PartialEq for SharedSpan<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start
+            && self.end == other.end
+            && self.cursor == other.cursor
+            && self.snippet == other.snippet
+    }
+}
+
+impl<T: ?Sized + Buffer> Show for SharedSpan<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::show_span(self, f)
+    }
+}
+
+/// An input over a reference-counted buffer, either an `Arc<str>` or an
+/// `Arc<[u8]>`, whose slices and contexts hold a reference to the buffer
+/// instead of borrowing from it.
+///
+/// Parsed values and errors built from a `Shared` input are thus `'static` and
+/// `Send`: they can outlive the input, be cached, and move across threads.
+/// Slices are [`SharedSlice`]s, which are as cheap to create and clone as an
+/// `Arc`. Contexts are [`SharedSpan`]s with lines and columns counted in bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use pear::input::{Shared, SharedSlice};
+/// use pear::macros::parse;
+/// use pear::parsers::take_while;
+///
+/// fn first_word(source: String) -> SharedSlice<str> {
+///     let input: Shared<str> = Shared::from(source);
+///     parse!(take_while(|c| c.is_alphabetic()): input).unwrap()
+/// }
+///
+/// let word = std::thread::spawn(|| first_word("hello".into())).join().unwrap();
+/// assert_eq!(word, "hello");
+/// ```
+pub struct Shared<T: ?Sized + Buffer> {
+    source: Arc<T>,
+    pos: usize,
+    lines: OnceLock<LineIndex>,
+}
+
+impl<T: ?Sized + Buffer + fmt::Debug> fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shared")
+            .field("source", &self.source)
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
+
+impl<T: ?Sized + Buffer> From<Arc<T>> for Shared<T> {
+    fn from(source: Arc<T>) -> Self {
+        Shared { source, pos: 0, lines: OnceLock::new() }
+    }
+}
+
+impl From<&str> for Shared<str> {
+    fn from(source: &str) -> Self {
+        Shared::from(Arc::<str>::from(source))
+    }
+}
+
+impl From<String> for Shared<str> {
+    fn from(source: String) -> Self {
+        Shared::from(Arc::<str>::from(source))
+    }
+}
+
+impl From<&[u8]> for Shared<[u8]> {
+    fn from(source: &[u8]) -> Self {
+        Shared::from(Arc::<[u8]>::from(source))
+    }
+}
+
+impl From<Vec<u8>> for Shared<[u8]> {
+    fn from(source: Vec<u8>) -> Self {
+        Shared::from(Arc::<[u8]>::from(source))
+    }
+}
+
+impl<T: ?Sized + Buffer> Shared<T> {
+    /// Returns the buffer being parsed.
+    pub fn source(&self) -> &Arc<T> {
+        &self.source
+    }
+
+    /// Returns the 1-indexed line and column, counted in bytes, of the byte at
+    /// `offset`.
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        self.lines
+            .get_or_init(|| LineIndex::new(self.source.bytes()))
+            .position(offset)
+    }
+
+    fn shared(&self, start: usize, end: usize) -> SharedSlice<T> {
+        SharedSlice { source: self.source.clone(), start, end }
+    }
+
+    fn rest(&self) -> &T {
+        self.source.range(self.pos..self.source.buffer_len()).expect("valid position")
+    }
+}
+
+impl<T: ?Sized + Buffer> Input for Shared<T> {
+    type Token = T::Token;
+    type Slice = SharedSlice<T>;
+    type Many = Self::Slice;
+
+    type Marker = usize;
+    type Context = SharedSpan<T>;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.rest().first_token().map(|(token, _)| token)
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        let end = self.pos.checked_add(n)?;
+        self.source.range(self.pos..end)?;
+        Some(self.shared(self.pos, end))
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.token().map(|t| cond(&t)).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, mut cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let (token, len) = self.rest().first_token()?;
+        if cond(&token) {
+            self.pos += len;
+            return Some(token);
+        }
+
+        None
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.pos += n;
+            return Some(slice);
+        }
+
+        None
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let start = self.pos;
+        while self.eat(&mut cond).is_some() { }
+        self.shared(start, self.pos)
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, mut cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let mut skipped = 0;
+        while self.eat(&mut cond).is_some() {
+            skipped += 1;
+        }
+
+        skipped
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.source.buffer_len() - self.pos >= n
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.pos
+    }
+
+    fn progress(&mut self) -> Option<usize> {
+        Some(self.pos)
+    }
+
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        let cursor = self.token();
+        if self.pos == 0 {
+            let start = (1, 1, 0);
+            return SharedSpan { start, end: start, cursor, snippet: None };
+        }
+
+        let (start_line, start_col) = self.position(mark);
+        let (end_line, end_col) = self.position(self.pos);
+        SharedSpan {
+            start: (start_line, start_col, mark),
+            end: (end_line, end_col, self.pos),
+            cursor,
+            snippet: Some(self.shared(mark, self.pos)),
+        }
+    }
+}
+
+impl<T: ?Sized + Buffer> Rewind for Shared<T> {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.pos = marker;
+    }
+}
+
+impl<T: ?Sized + Buffer> Seek for Shared<T> {
+    fn seek_to(&mut self, offset: usize) -> bool {
+        match self.source.range(offset..self.source.buffer_len()) {
+            Some(_) => {
+                self.pos = offset;
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -84,7 +84,8 @@ impl Show for u8 {
 
 impl_show_with! { Debug,
         u16, u32, u64, u128, usize,
-    i8, i16, i32, i64, i128, isize
+    i8, i16, i32, i64, i128, isize,
+    bool
 }
 
 macro_rules! impl_with_tick_display {
@@ -99,5 +100,5 @@ macro_rules! impl_with_tick_display {
 }
 
 impl_with_tick_display! {
-    &str, String, char, std::borrow::Cow<'static, str>,
+    &str, String, char, std::borrow::Cow<'_, str>,
 }
@@ -0,0 +1,112 @@
+use crate::input::Text;
+
+/// Identifies a source registered in a [`SourceMap`].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct FileId(usize);
+
+/// The identity of a source file as recorded in a [`Span`](crate::input::Span).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct SourceFile<'a> {
+    /// The file's identifier in its source map.
+    pub id: FileId,
+    /// The name the file was registered with, usually its path.
+    pub name: &'a str,
+}
+
+#[derive(Debug)]
+struct Source {
+    name: String,
+    text: String,
+    base: usize,
+}
+
+/// A collection of named sources, such as a configuration file and the files
+/// it includes, that hands out [`Text`] inputs tagged with their file.
+///
+/// Every source occupies its own range of offsets: the markers of a `Text`
+/// from a source map, and the offsets in its spans, are relative to the start
+/// of the map rather than to the start of the source. Offsets from different
+/// files are thus never equal, and [`SourceMap::file_at()`] can recover the
+/// file any offset belongs to.
+///
+/// # Example
+///
+/// ```rust
+/// use pear::input::SourceMap;
+/// use pear::macros::parse;
+/// use pear::parsers::eat_slice;
+///
+/// let mut map = SourceMap::new();
+/// let main = map.add("main.conf", "include = extra.conf");
+/// let extra = map.add("extra.conf", "key = value");
+///
+/// let error = parse!(eat_slice("key"): map.text(main)).unwrap_err();
+/// assert!(error.to_string().contains("main.conf:1:1"));
+///
+/// assert_eq!(map.file_at(map.base(extra) + 3), Some(extra));
+/// ```
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    sources: Vec<Source>,
+}
+
+impl SourceMap {
+    /// Creates an empty source map.
+    pub fn new() -> SourceMap {
+        SourceMap::default()
+    }
+
+    /// Registers a source named `name`, usually its path, with contents
+    /// `text`. Returns the identifier for the new source.
+    pub fn add<N, T>(&mut self, name: N, text: T) -> FileId
+        where N: Into<String>, T: Into<String>
+    {
+        // Leave a gap of one offset between files so that the EOF offset of a
+        // file is distinct from the first offset of the next.
+        let base = self.sources.last().map_or(0, |last| last.base + last.text.len() + 1);
+        self.sources.push(Source { name: name.into(), text: text.into(), base });
+        FileId(self.sources.len() - 1)
+    }
+
+    /// Returns a `Text` input over the source identified by `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` was not returned by this source map.
+    pub fn text(&self, id: FileId) -> Text<'_> {
+        let source = &self.sources[id.0];
+        let file = SourceFile { id, name: &source.name };
+        Text::from(&*source.text).in_file(file, source.base)
+    }
+
+    /// Returns the name of the source identified by `id`.
+    pub fn name(&self, id: FileId) -> &str {
+        &self.sources[id.0].name
+    }
+
+    /// Returns the contents of the source identified by `id`.
+    pub fn source(&self, id: FileId) -> &str {
+        &self.sources[id.0].text
+    }
+
+    /// Returns the offset at which the source identified by `id` begins.
+    pub fn base(&self, id: FileId) -> usize {
+        self.sources[id.0].base
+    }
+
+    /// Returns the file that `offset`, an offset as found in markers and
+    /// spans, belongs to, if any.
+    pub fn file_at(&self, offset: usize) -> Option<FileId> {
+        let index = self.sources.partition_point(|s| s.base <= offset).checked_sub(1)?;
+        let source = &self.sources[index];
+        match offset <= source.base + source.text.len() {
+            true => Some(FileId(index)),
+            false => None,
+        }
+    }
+
+    /// Returns an iterator over the identifiers of all registered sources.
+    pub fn files(&self) -> impl Iterator<Item = FileId> {
+        (0..self.sources.len()).map(FileId)
+    }
+}
@@ -99,4 +99,12 @@ impl<'a> Input for &'a str {
         let consumed = mark.len() - self.len();
         &mark[..consumed]
     }
+
+    /// A `&str` doesn't know where it started, so this isn't an offset: it is
+    /// `usize::MAX` less the length of what remains, which grows as the string
+    /// is consumed. It is only meaningful relative to the progress of the same
+    /// string at other positions.
+    fn progress(&mut self) -> Option<usize> {
+        Some(usize::MAX - self.len())
+    }
 }
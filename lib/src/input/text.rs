@@ -1,9 +1,65 @@
-pub use crate::input::{Input, Rewind, Show, ParserInfo};
+use std::sync::OnceLock;
+use std::fmt::{self, Formatter};
+use std::ops::Range;
+
+pub use crate::input::{Input, Rewind, Show, ParserInfo, SourceFile};
+use crate::input::line_index::LineIndex;
 
 #[cfg(feature = "color")]
 use yansi::Paint;
 
+/// The unit in which [`Text`] counts columns.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub enum ColumnUnit {
+    /// Columns are counted in bytes. This is the default.
+    #[default]
+    Byte,
+    /// Columns are counted in Unicode scalar values, i.e, `char`s.
+    Char,
+    /// Columns are counted in UTF-16 code units, as in the Language Server
+    /// Protocol's default position encoding.
+    Utf16,
+    /// Columns are counted in `char`s except that a tab advances the column to
+    /// the next multiple of `tab_width`, as an editor would display it.
+    Visual {
+        /// The distance between tab stops.
+        tab_width: usize
+    },
+}
+
+impl ColumnUnit {
+    /// Returns the 1-indexed column just past `prefix`, the text between the
+    /// start of a line and the position in question.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pear::input::ColumnUnit;
+    ///
+    /// assert_eq!(ColumnUnit::Byte.column("é😀"), 7);
+    /// assert_eq!(ColumnUnit::Char.column("é😀"), 3);
+    /// assert_eq!(ColumnUnit::Utf16.column("é😀"), 4);
+    /// assert_eq!(ColumnUnit::Visual { tab_width: 4 }.column("a\tb"), 6);
+    /// ```
+    pub fn column(self, prefix: &str) -> usize {
+        let width = match self {
+            ColumnUnit::Byte => prefix.len(),
+            ColumnUnit::Char => prefix.chars().count(),
+            ColumnUnit::Utf16 => prefix.chars().map(char::len_utf16).sum(),
+            ColumnUnit::Visual { tab_width } => prefix.chars().fold(0, |col, c| {
+                match c {
+                    '\t' if tab_width > 0 => (col / tab_width + 1) * tab_width,
+                    _ => col + 1,
+                }
+            }),
+        };
+
+        width + 1
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
 pub struct Span<'a> {
     /// Start line/column/offset.
     pub start: (usize, usize, usize),
@@ -13,79 +69,259 @@ pub struct Span<'a> {
     pub cursor: Option<char>,
     /// Snippet between start and end.
     pub snippet: Option<&'a str>,
+    /// The unit in which the columns in `start` and `end` are counted.
+    pub columns: ColumnUnit,
+    /// The source file, if the text came from a
+    /// [`SourceMap`](crate::input::SourceMap).
+    pub file: Option<SourceFile<'a>>,
+}
+
+impl<'a> Span<'a> {
+    /// Returns a span from `start` to `end` with columns counted in bytes and
+    /// no source file.
+    pub const fn new(
+        start: (usize, usize, usize),
+        end: (usize, usize, usize),
+        cursor: Option<char>,
+        snippet: Option<&'a str>,
+    ) -> Self {
+        Span { start, end, cursor, snippet, columns: ColumnUnit::Byte, file: None }
+    }
+
+    /// Returns `self` with its columns counted in `columns`.
+    pub const fn with_columns(self, columns: ColumnUnit) -> Self {
+        Span { columns, ..self }
+    }
+
+    /// Returns `self` attributed to the source file `file`.
+    pub const fn with_file(self, file: SourceFile<'a>) -> Self {
+        Span { file: Some(file), ..self }
+    }
 }
 
 const SNIPPET_LEN: usize = 30;
 
 impl<'a> Show for Span<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let (a, b, _) = self.start;
-        let (c, d, _) = self.end;
+        let file = self.file.map(|file| file.name);
+        let cursor = self.cursor.map(|c| c.escape_debug());
+        show_span(f, file, self.start, self.end, self.snippet, cursor)
+    }
+}
 
-        if self.start == self.end {
-            write!(f, "{}:{}", a, b)?;
-        } else {
-            write!(f, "{}:{} to {}:{}", a, b, c, d)?;
-        }
+/// A snippet of input that a span displays escaped.
+pub(crate) trait Snippet {
+    fn len(&self) -> usize;
 
-        let write_snippet = |f: &mut std::fmt::Formatter<'_>, snippet: &str| {
-            for c in snippet.escape_debug() { write!(f, "{}", c)?; }
-            Ok(())
-        };
+    fn fmt_escaped(&self, range: Range<usize>, f: &mut Formatter<'_>) -> fmt::Result;
+}
 
-        if let Some(snippet) = self.snippet {
-            write!(f, " \"")?;
-            if snippet.len() > SNIPPET_LEN + 6 {
-                write_snippet(f, &snippet[..SNIPPET_LEN / 2])?;
+impl Snippet for str {
+    fn len(&self) -> usize {
+        str::len(self)
+    }
 
-                #[cfg(feature = "color")]
-                write!(f, " {} ", "...".blue())?;
+    fn fmt_escaped(&self, range: Range<usize>, f: &mut Formatter<'_>) -> fmt::Result {
+        for c in self[range].escape_debug() { write!(f, "{}", c)?; }
+        Ok(())
+    }
+}
 
-                #[cfg(not(feature = "color"))]
-                write!(f, " ... ")?;
+impl Snippet for [u8] {
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
 
-                let end_start = snippet.len() - SNIPPET_LEN / 2;
-                write_snippet(f, &snippet[end_start..])?;
-            } else {
-                write_snippet(f, snippet)?;
-            }
+    fn fmt_escaped(&self, range: Range<usize>, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self[range].escape_ascii())
+    }
+}
 
-            if let Some(cursor) = self.cursor {
-                #[cfg(feature = "color")]
-                write!(f, "{}", cursor.escape_debug().blue())?;
+/// Displays a span as `file:line:col to line:col "snippet"`, with long
+/// snippets elided in the middle. Shared by [`Span`] and
+/// [`ByteSpan`](crate::input::ByteSpan).
+pub(crate) fn show_span<S: Snippet + ?Sized, C: fmt::Display>(
+    f: &mut Formatter<'_>,
+    file: Option<&str>,
+    start: (usize, usize, usize),
+    end: (usize, usize, usize),
+    snippet: Option<&S>,
+    cursor: Option<C>,
+) -> fmt::Result {
+    let (a, b, _) = start;
+    let (c, d, _) = end;
+
+    if let Some(file) = file {
+        write!(f, "{}:", file)?;
+    }
 
-                #[cfg(not(feature = "color"))]
-                write!(f, "{}", cursor.escape_debug())?;
-            }
+    if start == end {
+        write!(f, "{}:{}", a, b)?;
+    } else {
+        write!(f, "{}:{} to {}:{}", a, b, c, d)?;
+    }
+
+    if let Some(snippet) = snippet {
+        write!(f, " \"")?;
+        if snippet.len() > SNIPPET_LEN + 6 {
+            snippet.fmt_escaped(0..SNIPPET_LEN / 2, f)?;
+
+            #[cfg(feature = "color")]
+            write!(f, " {} ", "...".blue())?;
+
+            #[cfg(not(feature = "color"))]
+            write!(f, " ... ")?;
 
-            write!(f, "\"")?;
+            let end_start = snippet.len() - SNIPPET_LEN / 2;
+            snippet.fmt_escaped(end_start..snippet.len(), f)?;
         } else {
+            snippet.fmt_escaped(0..snippet.len(), f)?;
+        }
+
+        if let Some(cursor) = cursor {
             #[cfg(feature = "color")]
-            write!(f, " {}", "[EOF]".blue())?;
+            write!(f, "{}", cursor.blue())?;
 
             #[cfg(not(feature = "color"))]
-            write!(f, " [EOF]")?;
+            write!(f, "{}", cursor)?;
         }
 
-        Ok(())
+        write!(f, "\"")?;
+    } else {
+        #[cfg(feature = "color")]
+        write!(f, " {}", "[EOF]".blue())?;
+
+        #[cfg(not(feature = "color"))]
+        write!(f, " [EOF]")?;
     }
+
+    Ok(())
 }
 
 #[derive(Debug)]
 pub struct Text<'a> {
     current: &'a str,
     start: &'a str,
+    lines: OnceLock<LineIndex>,
+    columns: ColumnUnit,
+    file: Option<SourceFile<'a>>,
+    // The offset of `start`. Markers and offsets in spans are relative to it.
+    base: usize,
 }
 
 impl<'a> From<&'a str> for Text<'a> {
     fn from(start: &'a str) -> Text<'a> {
-        Text { start, current: start }
+        Text {
+            start,
+            current: start,
+            lines: OnceLock::new(),
+            columns: ColumnUnit::Byte,
+            file: None,
+            base: 0,
+        }
+    }
+}
+
+impl<'a> Text<'a> {
+    pub(crate) fn in_file(mut self, file: SourceFile<'a>, base: usize) -> Self {
+        self.file = Some(file);
+        self.base = base;
+        self
+    }
+
+    /// Returns the source file the text came from, if any.
+    pub fn file(&self) -> Option<SourceFile<'a>> {
+        self.file
+    }
+
+    /// Sets the unit in which columns in positions and spans are counted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pear::input::{Text, ColumnUnit};
+    ///
+    /// let text = Text::from("\tλx").with_columns(ColumnUnit::Visual { tab_width: 8 });
+    /// assert_eq!(text.position(3), (1, 10));
+    /// ```
+    pub fn with_columns(mut self, columns: ColumnUnit) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Returns the unit in which columns are counted.
+    pub fn columns(&self) -> ColumnUnit {
+        self.columns
+    }
+
+    /// Returns the 1-indexed line and column of the byte at `offset`. Columns
+    /// are counted in the unit set via [`Text::with_columns()`], bytes by
+    /// default. Like markers, `offset` is relative to the start of the
+    /// [`SourceMap`](crate::input::SourceMap) the text came from, if any.
+    ///
+    /// An `offset` past the end of the text is clamped to the end, one before
+    /// its start to the start, and one inside of a character to the start of
+    /// that character.
+    ///
+    /// The first call builds a table of line starts for the entire text, so
+    /// this and every subsequent lookup is a binary search.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pear::input::Text;
+    ///
+    /// let text = Text::from("ab\ncd");
+    /// assert_eq!(text.position(0), (1, 1));
+    /// assert_eq!(text.position(2), (1, 3));
+    /// assert_eq!(text.position(4), (2, 2));
+    /// assert_eq!(text.position(9), (2, 3));
+    /// ```
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let mut offset = match offset.checked_sub(self.base) {
+            Some(offset) => std::cmp::min(offset, self.start.len()),
+            None => 0,
+        };
+
+        while !self.start.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let lines = self.lines.get_or_init(|| LineIndex::new(self.start.as_bytes()));
+        match self.columns {
+            ColumnUnit::Byte => lines.position(offset),
+            unit => {
+                let (line, start) = lines.line(offset);
+                (line, unit.column(&self.start[start..offset]))
+            }
+        }
+    }
+
+    /// Returns a span over the bytes in `start..end` of the text with cursor
+    /// `cursor`. Unlike markers, `start` and `end` are relative to the start
+    /// of the text, not to the start of its source map.
+    pub(crate) fn span(&self, start: usize, end: usize, cursor: Option<char>) -> Span<'a> {
+        let (start_line, start_col) = self.position(self.base + start);
+        let (end_line, end_col) = self.position(self.base + end);
+        Span {
+            start: (start_line, start_col, self.base + start),
+            end: (end_line, end_col, self.base + end),
+            cursor,
+            snippet: self.start.get(start..end),
+            columns: self.columns,
+            file: self.file,
+        }
+    }
+
+    /// Returns the entire text, regardless of how much has been consumed.
+    pub(crate) fn source(&self) -> &'a str {
+        self.start
     }
 }
 
 impl Rewind for Text<'_> {
     fn rewind_to(&mut self, marker: Self::Marker) {
-        self.current = &self.start[marker..];
+        self.current = &self.start[(marker - self.base)..];
     }
 }
 
@@ -160,25 +396,29 @@ impl<'a> Input for Text<'a> {
 
     #[inline(always)]
     fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
-        self.start.len() - self.current.len()
+        self.base + self.start.len() - self.current.len()
+    }
+
+    fn progress(&mut self) -> Option<usize> {
+        Some(self.base + self.start.len() - self.current.len())
     }
 
     fn context(&mut self, mark: Self::Marker) -> Self::Context {
         let cursor = self.token();
+        let (columns, file) = (self.columns, self.file);
         let bytes_read = self.start.len() - self.current.len();
         if bytes_read == 0 {
-            Span { start: (1, 1, 0), end: (1, 1, 0), snippet: None, cursor }
+            let start = (1, 1, self.base);
+            Span { start, end: start, snippet: None, cursor, columns, file }
         } else {
-            let start_offset = mark;
+            let start_offset = mark - self.base;
             let end_offset = bytes_read;
 
-            let to_start_str = &self.start[..start_offset];
-            let (start_line, start_col) = line_col(to_start_str);
-            let start = (start_line, start_col, start_offset);
+            let (start_line, start_col) = self.position(mark);
+            let start = (start_line, start_col, mark);
 
-            let to_current_str = &self.start[..bytes_read];
-            let (end_line, end_col) = line_col(to_current_str);
-            let end = (end_line, end_col, bytes_read);
+            let (end_line, end_col) = self.position(self.base + end_offset);
+            let end = (end_line, end_col, self.base + end_offset);
 
             let snippet = if end_offset <= self.start.len() {
                 Some(&self.start[start_offset..end_offset])
@@ -186,20 +426,7 @@ impl<'a> Input for Text<'a> {
                 None
             };
 
-            Span { start, end, cursor, snippet }
+            Span { start, end, cursor, snippet, columns, file }
         }
     }
 }
-
-fn line_col(string: &str) -> (usize, usize) {
-    if string.is_empty() {
-        return (1, 1);
-    }
-
-    let (line_count, last_line) = string.lines().enumerate().last().unwrap();
-    if string.ends_with('\n') {
-        (line_count + 2, 1)
-    } else {
-        (line_count + 1, last_line.len() + 1)
-    }
-}
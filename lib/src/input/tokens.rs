@@ -0,0 +1,212 @@
+use std::ops::Range;
+
+use crate::input::{Input, Rewind, Show, ParserInfo, Text, Span};
+
+/// Trait implemented by tokens that know where in the source text they were
+/// lexed from.
+pub trait Spanned {
+    /// Returns the byte range of the source text the token was lexed from.
+    fn span(&self) -> Range<usize>;
+}
+
+impl<T: Spanned> Spanned for &T {
+    fn span(&self) -> Range<usize> {
+        T::span(self)
+    }
+}
+
+/// An input over the output of a lexer: a slice of tokens, each of which
+/// records the range of the source text it was lexed from.
+///
+/// Tokens are parsed like the items of a [`Cursor`](crate::input::Cursor),
+/// but contexts are [`Span`]s over the original source text, so errors from
+/// the parsing stage point at the characters the offending tokens came from.
+/// Markers are token indices.
+///
+/// # Example
+///
+/// ```rust
+/// use std::ops::Range;
+/// use pear::input::{Tokens, Spanned};
+/// use pear::macros::parse;
+/// use pear::parsers::eat;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Word(Range<usize>);
+///
+/// impl Spanned for Word {
+///     fn span(&self) -> Range<usize> { self.0.clone() }
+/// }
+///
+/// # impl pear::input::Show for Word {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// #         write!(f, "{:?}", self)
+/// #     }
+/// # }
+/// let source = "hello\n  world";
+/// let words = [Word(0..5), Word(8..13)];
+/// let error = parse!(eat(Word(0..5)): Tokens::new(source, &words)).unwrap_err();
+/// assert_eq!(error.info.context.start, (2, 3, 8));
+/// assert_eq!(error.info.context.cursor, Some('w'));
+/// ```
+#[derive(Debug)]
+pub struct Tokens<'a, T> {
+    text: Text<'a>,
+    start: &'a [T],
+    current: &'a [T],
+}
+
+impl<'a, T: Spanned> Tokens<'a, T> {
+    /// Creates an input over `tokens`, lexed from `text`. `text` is usually a
+    /// `&str` but may be any [`Text`], such as one from a
+    /// [`SourceMap`](crate::input::SourceMap) or with a different
+    /// [`ColumnUnit`](crate::input::ColumnUnit).
+    pub fn new<S: Into<Text<'a>>>(text: S, tokens: &'a [T]) -> Self {
+        Tokens { text: text.into(), start: tokens, current: tokens }
+    }
+
+    /// Returns the source text the tokens were lexed from.
+    pub fn source(&self) -> &'a str {
+        self.text.source()
+    }
+
+    /// Returns the span of the source text covered by the tokens in `range`.
+    /// An empty range yields an empty span at the start of the token at
+    /// `range.start` or, if there is none, at the end of the last token.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn span(&self, range: Range<usize>) -> Span<'a> {
+        let cursor = self.start.get(range.end)
+            .and_then(|token| self.source().get(token.span().start..)?.chars().next());
+
+        let index = range.start;
+        let (start, end) = match &self.start[range] {
+            [] => (self.offset_at(index), self.offset_at(index)),
+            [first, .., last] => (first.span().start, last.span().end),
+            [only] => (only.span().start, only.span().end),
+        };
+
+        self.text.span(start, end, cursor)
+    }
+
+    fn offset(&self) -> usize {
+        self.start.len() - self.current.len()
+    }
+
+    // The source offset of the token at `index`, or the end of the last token.
+    fn offset_at(&self, index: usize) -> usize {
+        match self.start.get(index) {
+            Some(token) => token.span().start,
+            None => self.start.last().map_or(0, |token| token.span().end),
+        }
+    }
+}
+
+impl<'a, T: Spanned + Clone + Show + PartialEq> Input for Tokens<'a, T> {
+    type Token = T;
+    type Slice = &'a [T];
+    type Many = Self::Slice;
+
+    type Marker = usize;
+    type Context = Span<'a>;
+
+    /// Returns a copy of the current token, if there is one.
+    fn token(&mut self) -> Option<Self::Token> {
+        self.current.first().cloned()
+    }
+
+    /// Returns a copy of the current slice of size `n`, if there is one.
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.current.get(..n)
+    }
+
+    /// Checks if the current token fulfills `cond`.
+    fn peek<F>(&mut self, mut cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.current.first().map(&mut cond).unwrap_or(false)
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`.
+    fn peek_slice<F>(&mut self, n: usize, mut cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.slice(n).map(|s| cond(&s)).unwrap_or(false)
+    }
+
+    /// Checks if the current token fulfills `cond`. If so, the token is
+    /// consumed and returned. Otherwise, returns `None`.
+    fn eat<F>(&mut self, cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        if self.peek(cond) {
+            let token = self.current[0].clone();
+            self.current = &self.current[1..];
+            return Some(token);
+        }
+
+        None
+    }
+
+    /// Checks if the current slice of size `n` (if any) fulfills `cond`. If so,
+    /// the slice is consumed and returned. Otherwise, returns `None`.
+    fn eat_slice<F>(&mut self, n: usize, mut cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        let slice = self.slice(n)?;
+        if cond(&slice) {
+            self.current = &self.current[n..];
+            return Some(slice);
+        }
+
+        None
+    }
+
+    /// Takes tokens while `cond` returns true, collecting them into a
+    /// `Self::Many` and returning it.
+    fn take<F>(&mut self, mut cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        let consumed = self.current.iter()
+            .position(|t| !cond(t))
+            .unwrap_or(self.current.len());
+
+        let value = &self.current[..consumed];
+        self.current = &self.current[consumed..];
+        value
+    }
+
+    /// Skips tokens while `cond` returns true. Returns the number of skipped
+    /// tokens.
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.take(cond).len()
+    }
+
+    /// Returns `true` if there are at least `n` tokens remaining.
+    fn has(&mut self, n: usize) -> bool {
+        self.current.len() >= n
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, _: &ParserInfo) -> Self::Marker {
+        self.offset()
+    }
+
+    fn progress(&mut self) -> Option<usize> {
+        Some(self.offset())
+    }
+
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        self.span(mark..self.offset())
+    }
+}
+
+impl<T: Spanned + Clone + Show + PartialEq> Rewind for Tokens<'_, T> {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.current = &self.start[marker..];
+    }
+}
@@ -0,0 +1,276 @@
+use std::io;
+use std::sync::Arc;
+
+use crate::input::{Input, Rewind, ParserInfo};
+
+/// Trait implemented by types that skip trivia, such as whitespace and
+/// comments, at the current position of an input of type `I`.
+///
+/// Implemented by all `FnMut(&mut I)` closures and by [`Whitespace`].
+pub trait Skip<I: Input> {
+    /// Skips all trivia at the current position of `input`.
+    fn skip(&mut self, input: &mut I);
+}
+
+impl<I: Input, F: FnMut(&mut I)> Skip<I> for F {
+    #[inline(always)]
+    fn skip(&mut self, input: &mut I) {
+        self(input)
+    }
+}
+
+/// A [`Skip`] implementation for text inputs that skips whitespace and,
+/// optionally, line and block comments.
+///
+/// # Example
+///
+/// ```rust
+/// use pear::input::Whitespace;
+///
+/// // Skip whitespace, `# line` comments, and `/* block */` comments.
+/// let skip = Whitespace::new().line_comments("#").block_comments("/*", "*/");
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Whitespace {
+    line: Option<&'static str>,
+    block: Option<(&'static str, &'static str)>,
+}
+
+impl Whitespace {
+    /// A skipper for whitespace, as determined by [`char::is_whitespace()`],
+    /// alone.
+    pub fn new() -> Self {
+        Whitespace::default()
+    }
+
+    /// Additionally skip comments that start with `start` and run until the
+    /// end of the line.
+    pub fn line_comments(mut self, start: &'static str) -> Self {
+        self.line = Some(start);
+        self
+    }
+
+    /// Additionally skip comments delimited by `start` and `end`. Block
+    /// comments do not nest. An unterminated block comment extends to the end
+    /// of the input.
+    pub fn block_comments(mut self, start: &'static str, end: &'static str) -> Self {
+        self.block = Some((start, end));
+        self
+    }
+}
+
+impl<I> Skip<I> for Whitespace
+    where I: Input<Token = char>, I::Slice: PartialEq<&'static str>
+{
+    fn skip(&mut self, input: &mut I) {
+        loop {
+            input.skip(|c| c.is_whitespace());
+            if let Some(start) = self.line {
+                if input.eat_slice(start.len(), |s| s == &start).is_some() {
+                    input.skip(|&c| c != '\n');
+                    continue;
+                }
+            }
+
+            if let Some((start, end)) = self.block {
+                if input.eat_slice(start.len(), |s| s == &start).is_some() {
+                    while input.eat_slice(end.len(), |s| s == &end).is_none() {
+                        if input.eat(|_| true).is_none() {
+                            break;
+                        }
+                    }
+
+                    continue;
+                }
+            }
+
+            break;
+        }
+    }
+}
+
+/// An input that wraps `I`, transparently skipping trivia such as whitespace
+/// and comments before every token-level operation.
+///
+/// Trivia is skipped with `S`, either a [`Whitespace`] or a closure that
+/// consumes trivia from `I`. Because [`mark()`](Input::mark()) skips trivia
+/// too, contexts begin at the first token a parser consumed and never include
+/// leading trivia. Trivia is only skipped _before_ an operation: `take()`
+/// stops at the first token that doesn't match, trivia or not.
+///
+/// In _raw_ mode, no trivia is skipped. Use the
+/// [`raw()`](crate::combinators::raw()) combinator to parse items like string
+/// literals, where whitespace and comment delimiters are significant.
+///
+/// # Example
+///
+/// ```rust
+/// use pear::input::{Pear, Text, Trivia, Whitespace};
+/// use pear::macros::{parser, parse};
+/// use pear::parsers::*;
+///
+/// type Input<'a> = Pear<Trivia<Text<'a>, Whitespace>>;
+/// type Result<'a, T> = pear::input::Result<T, Trivia<Text<'a>, Whitespace>>;
+///
+/// #[parser]
+/// fn pair<'a>(input: &mut Input<'a>) -> Result<'a, (&'a str, &'a str)> {
+///     let key = take_some_while(|c| c.is_alphabetic())?;
+///     eat('=')?;
+///     let value = take_some_while(|c| c.is_alphabetic())?;
+///     (key, value)
+/// }
+///
+/// let skip = Whitespace::new().line_comments("#");
+/// let input = Trivia::new(Text::from("  key # the key\n =\tvalue \n"), skip);
+/// assert_eq!(parse!(pair: input).unwrap(), ("key", "value"));
+/// ```
+#[derive(Debug)]
+pub struct Trivia<I, S> {
+    input: I,
+    skipper: S,
+    raw: bool,
+}
+
+impl<I: Input, S: Skip<I>> Trivia<I, S> {
+    /// Wraps `input`, skipping trivia with `skipper`.
+    pub fn new(input: I, skipper: S) -> Self {
+        Trivia { input, skipper, raw: false }
+    }
+
+    /// Returns `true` if the input is in raw mode.
+    pub fn is_raw(&self) -> bool {
+        self.raw
+    }
+
+    /// Enables raw mode, in which trivia isn't skipped, if `raw` is `true`, and
+    /// disables it otherwise. Returns the previous mode.
+    pub fn set_raw(&mut self, raw: bool) -> bool {
+        std::mem::replace(&mut self.raw, raw)
+    }
+
+    /// Returns a reference to the wrapped input.
+    pub fn get_ref(&self) -> &I {
+        &self.input
+    }
+
+    /// Consumes `self`, returning the wrapped input.
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    #[inline(always)]
+    fn skip_trivia(&mut self) -> &mut I {
+        if !self.raw {
+            self.skipper.skip(&mut self.input);
+        }
+
+        &mut self.input
+    }
+}
+
+impl<I: Input, S: Skip<I>> Input for Trivia<I, S> {
+    type Token = I::Token;
+    type Slice = I::Slice;
+    type Many = I::Many;
+
+    type Marker = I::Marker;
+    type Context = I::Context;
+
+    #[inline(always)]
+    fn token(&mut self) -> Option<Self::Token> {
+        self.skip_trivia().token()
+    }
+
+    #[inline(always)]
+    fn slice(&mut self, n: usize) -> Option<Self::Slice> {
+        self.skip_trivia().slice(n)
+    }
+
+    #[inline(always)]
+    fn has(&mut self, n: usize) -> bool {
+        self.skip_trivia().has(n)
+    }
+
+    #[inline(always)]
+    fn peek<F>(&mut self, cond: F) -> bool
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.skip_trivia().peek(cond)
+    }
+
+    #[inline(always)]
+    fn peek_slice<F>(&mut self, n: usize, cond: F) -> bool
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.skip_trivia().peek_slice(n, cond)
+    }
+
+    #[inline(always)]
+    fn eat<F>(&mut self, cond: F) -> Option<Self::Token>
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.skip_trivia().eat(cond)
+    }
+
+    #[inline(always)]
+    fn eat_slice<F>(&mut self, n: usize, cond: F) -> Option<Self::Slice>
+        where F: FnMut(&Self::Slice) -> bool
+    {
+        self.skip_trivia().eat_slice(n, cond)
+    }
+
+    #[inline(always)]
+    fn take<F>(&mut self, cond: F) -> Self::Many
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.skip_trivia().take(cond)
+    }
+
+    #[inline(always)]
+    fn skip<F>(&mut self, cond: F) -> usize
+        where F: FnMut(&Self::Token) -> bool
+    {
+        self.skip_trivia().skip(cond)
+    }
+
+    #[inline(always)]
+    fn mark(&mut self, info: &ParserInfo) -> Self::Marker {
+        self.skip_trivia().mark(info)
+    }
+
+    #[inline(always)]
+    fn context(&mut self, mark: Self::Marker) -> Self::Context {
+        self.input.context(mark)
+    }
+
+    #[inline(always)]
+    fn unmark(&mut self, marker: Self::Marker) {
+        self.input.unmark(marker)
+    }
+
+    #[inline(always)]
+    fn progress(&mut self) -> Option<usize> {
+        self.skip_trivia().progress()
+    }
+
+    #[inline(always)]
+    fn io_error(&mut self) -> Option<Arc<io::Error>> {
+        self.input.io_error()
+    }
+
+    #[inline(always)]
+    fn invalid(&mut self) -> Option<&'static str> {
+        self.input.invalid()
+    }
+
+    #[inline(always)]
+    fn is_complete(&mut self) -> bool {
+        self.input.is_complete()
+    }
+}
+
+impl<I: Input + Rewind, S: Skip<I>> Rewind for Trivia<I, S> {
+    fn rewind_to(&mut self, marker: Self::Marker) {
+        self.input.rewind_to(marker)
+    }
+}
@@ -18,7 +18,7 @@ pub use pear_codegen::{parser, switch};
 #[doc(inline)]
 pub use crate::{parse, parse_declare, parse_error, parse_try, is_parse_debug};
 #[doc(inline)]
-pub use crate::{parse_current_marker, parse_last_marker, parse_mark, parse_context};
+pub use crate::{parse_current_marker, parse_last_marker, parse_mark, parse_context, cut};
 #[doc(inline)]
 pub use crate::impl_show_with;
 
@@ -26,33 +26,61 @@ pub use crate::impl_show_with;
 ///
 /// Returns the combined result.
 ///
+/// When prefixed with `recover`, parsing continues past errors recovered from
+/// via [`combinators::recover()`] and friends, and a tuple of the parser's
+/// output, if it didn't fail outright, and all diagnostics is returned instead.
+/// An error from the parser or from `eof()` is the last diagnostic. The
+/// diagnostics are of the parser's error type, which must convert from
+/// [`Expected`].
+///
 /// Syntax:
 ///
 /// ```text
-/// parse := PARSER_NAME ( '(' (EXPR ',')* ')' )? ':' INPUT_EXPR
+/// parse := 'recover'? PARSER_NAME ( '(' (EXPR ',')* ')' )? ':' INPUT_EXPR
 ///
 /// PARSER_NAME := rust identifier to parser function
 /// INPUT_EXPR := any valid rust expression which resolves to a mutable
 ///               reference to type that implements `Input`
 /// ```
+///
+/// [`parsers::eof()`]: crate::parsers::eof()
+/// [`combinators::recover()`]: crate::combinators::recover()
+/// [`Expected`]: crate::error::Expected
 #[macro_export]
 macro_rules! parse {
+    (recover $parser:ident : &mut $e:expr) => ({
+        let input = &mut $e;
+        let result = $parser(input);
+        $crate::combinators::recovered(input, result)
+    });
+    (recover $parser:ident : $e:expr) => (parse!(recover $parser(): $e));
+    (recover $parser:ident ($($x:expr),*) : $e:expr) => ({
+        let mut input = $crate::input::Pear::with_error_type($e.into());
+        let result = $parser(&mut input $(, $x)*);
+        $crate::combinators::recovered(&mut input, result)
+    });
     ($parser:ident : &mut $e:expr) => ({
         let input = &mut $e;
-        (move || {
+        #[allow(clippy::result_large_err)]
+        let result = (move || {
             let result = $parser(input)?;
             $crate::parsers::eof(input).map_err(|e| e.into())?;
             $crate::result::IntoResult::into_result(result)
-        })()
+        })();
+
+        result
     });
     ($parser:ident : $e:expr) => (parse!($parser(): $e));
     ($parser:ident ($($x:expr),*) : $e:expr) => ({
         let mut input: $crate::input::Pear<_> = $e.into();
-        (move || {
+        #[allow(clippy::result_large_err)]
+        let result = (move || {
             let result = $parser(&mut input $(, $x)*)?;
             $crate::parsers::eof(&mut input).map_err(|e| e.into())?;
             $crate::result::IntoResult::into_result(result)
-        })()
+        })();
+
+        result
     })
 }
 
@@ -112,7 +140,9 @@ macro_rules! parse_last_marker {
     ([$n:expr; $i:expr; $marker:expr; $T:ty]) => (*$marker);
 }
 
-/// Return the mark at the current parsing position.
+/// Return the mark at the current parsing position. The marker should be
+/// passed to [`Input::unmark()`](crate::input::Input::unmark()) once it's no
+/// longer needed.
 ///
 /// Invoked with no arguments: `parse_current_marker!()`
 #[macro_export]
@@ -126,7 +156,8 @@ macro_rules! parse_current_marker {
 #[macro_export]
 macro_rules! parse_mark {
     ([$info:expr; $input:expr; $marker:expr; $T:ty]) => {{
-        *$marker = $crate::input::Input::mark($input, $info);
+        let ___old = ::std::mem::replace($marker, $crate::input::Input::mark($input, $info));
+        $crate::input::Input::unmark($input, ___old);
     }}
 }
 
@@ -140,6 +171,20 @@ macro_rules! parse_context {
     );
 }
 
+/// Commits to the alternative being parsed via [`Pear::cut()`]: if parsing
+/// fails from here on, enclosing `switch!`s try no further alternatives and
+/// instead return the error.
+///
+/// Invoked with no arguments: `cut!()`
+///
+/// [`Pear::cut()`]: crate::input::Pear::cut()
+#[macro_export]
+macro_rules! cut {
+    ([$n:expr; $input:expr; $m:expr; $T:ty]) => (
+        $crate::input::Pear::cut($input)
+    );
+}
+
 /// Runs a parser returning `Some` if it succeeds or `None` otherwise.
 ///
 /// Take a single parser expression as input. Without additional arguments,
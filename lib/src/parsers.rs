@@ -1,38 +1,110 @@
 use crate::error::Expected;
-use crate::input::{Input, Pear, Length, Token, Slice, Result, Rewind};
+use crate::input::{Input, Pear, Length, Token, Slice, Result, Rewind, IgnoreCase, ParserInfo};
 use crate::combinators::succeeds;
 use crate::macros::parser;
 
+pub mod binary;
+pub mod bits;
+
 // TODO: provide more basic parsers in pear
 //   - [f32, f64, i8, i32, ..., bool, etc.]: one for all reasonable built-ins
 //   - quoted_string(allowed): '"' allowed* '"'
 //   - escaped string, with some way to configure escapes
 
+/// Returns the error to report when the input ran out while `needed` more
+/// tokens were required: `Expected::Io` if an I/O error stopped the input,
+/// `Expected::Other` if malformed data did, `Expected::Incomplete` if more
+/// tokens may still arrive, and `expected` otherwise. Marks the input as
+/// exhausted in all but the last case.
+#[inline]
+pub(crate) fn exhausted<I: Input, D>(
+    input: &mut Pear<I, D>,
+    needed: usize,
+    expected: Expected<I::Token, I::Slice>
+) -> Expected<I::Token, I::Slice> {
+    if let Some(error) = input.io_error() {
+        input.exhausted = true;
+        return Expected::io(error);
+    }
+
+    if let Some(invalid) = input.invalid() {
+        input.exhausted = true;
+        return Expected::from(invalid);
+    }
+
+    match input.is_complete() || needed == 0 {
+        true => expected,
+        false => {
+            input.exhausted = true;
+            Expected::incomplete(needed)
+        }
+    }
+}
+
+/// Returns how many tokens short of `n` the input is.
 #[inline]
-fn expected_token<T, I>(
-    input: &mut Pear<I>,
+pub(crate) fn missing<I: Input, D>(input: &mut Pear<I, D>, n: usize) -> usize {
+    (0..=n).rev().find(|&k| input.has(k)).map_or(n, |k| n - k)
+}
+
+/// Returns `true` if fewer than `n` tokens remain but the input isn't known to
+/// be over: either more tokens may still arrive or an I/O error or malformed
+/// data stopped it.
+#[inline]
+fn cut_short<I: Input, D>(input: &mut Pear<I, D>, n: usize) -> bool {
+    !input.has(n) && (!input.is_complete() || input.io_error().is_some() || input.invalid().is_some())
+}
+
+/// Whether a failed parser needs to describe what it expected: to report it
+/// or to record it for furthest-failure tracking.
+#[inline(always)]
+fn describe<I: Input, D>(input: &Pear<I, D>) -> bool {
+    input.emit_error || input.options.track_furthest
+}
+
+/// Like `parse_error!` but for errors describing what a parser expected at the
+/// current position, which take part in furthest-failure tracking.
+macro_rules! parse_expected {
+    ([$info:expr; $input:expr; $marker:expr; $T:ty] $expected:expr) => ({
+        let expected = $expected;
+        $input.fail(*$info, *$marker, expected)
+    })
+}
+
+#[inline]
+fn expected_token<T, I, D>(
+    input: &mut Pear<I, D>,
     token: Option<T>
 ) -> Expected<I::Token, I::Slice>
     where T: Token<I>, I: Input
 {
     // TODO: Have some way to test this is being called minimally.
-    if input.emit_error {
-        Expected::token(token.as_ref(), input.token())
+    if describe(input) {
+        match input.token() {
+            Some(found) => Expected::token(token.as_ref(), Some(found)),
+            None => exhausted(input, 1, Expected::token(token.as_ref(), None)),
+        }
     } else {
         Expected::Elided
     }
 }
 
 #[inline]
-fn expected_slice<S, I>(
-    input: &mut Pear<I>,
+fn expected_slice<S, I, D>(
+    input: &mut Pear<I, D>,
     slice: S
 ) -> Expected<I::Token, I::Slice>
     where S: Slice<I>, I: Input
 {
     // TODO: Have some way to test this is being called minimally.
-    if input.emit_error {
-        Expected::slice(Some(&slice), input.slice(slice.len()))
+    if describe(input) {
+        match input.slice(slice.len()) {
+            Some(found) => Expected::slice(Some(&slice), Some(found)),
+            None => {
+                let needed = missing(input, slice.len());
+                exhausted(input, needed, Expected::slice(Some(&slice), None))
+            }
+        }
     } else {
         Expected::Elided
     }
@@ -40,38 +112,38 @@ fn expected_slice<S, I>(
 
 /// Eats the current token if it is `token`.
 #[parser(raw)]
-pub fn eat<I, T>(input: &mut Pear<I>, token: T) -> Result<I::Token, I>
+pub fn eat<I, T, D>(input: &mut Pear<I, D>, token: T) -> Result<I::Token, I>
     where I: Input, T: Token<I>
 {
     match input.eat(|t| &token == t) {
         Some(token) => Ok(token),
-        None => return parse_error!(expected_token(input, Some(token)))
+        None => return parse_expected!(expected_token(input, Some(token)))
     }
 }
 
 /// Eats the token `token` if `cond` holds on the current token.
 #[parser(raw)]
-pub fn eat_if<I, F>(input: &mut Pear<I>, cond: F) -> Result<I::Token, I>
+pub fn eat_if<I, F, D>(input: &mut Pear<I, D>, cond: F) -> Result<I::Token, I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
     match input.eat(cond) {
         Some(token) => Ok(token),
-        None => parse_error!(expected_token::<I::Token, _>(input, None))
+        None => parse_expected!(expected_token::<I::Token, _, _>(input, None))
     }
 }
 
 /// Eats the current token unconditionally. Fails if there are no tokens.
 #[parser(raw)]
-pub fn eat_any<I: Input>(input: &mut Pear<I>) -> Result<I::Token, I> {
+pub fn eat_any<I: Input, D>(input: &mut Pear<I, D>) -> Result<I::Token, I> {
     match input.eat(|_| true) {
         Some(token) => Ok(token),
-        None => return parse_error!(Expected::Token(None, None))
+        None => return parse_expected!(exhausted(input, 1, Expected::Token(None, None)))
     }
 }
 
 /// Skips the current token unconditionally. Fails if there are no tokens.
 #[parser(raw)]
-pub fn skip_any<I: Input>(input: &mut Pear<I>) -> Result<(), I> {
+pub fn skip_any<I: Input, D>(input: &mut Pear<I, D>) -> Result<(), I> {
     let mut skipped = false;
     input.skip(|_| {
         if !skipped {
@@ -84,113 +156,260 @@ pub fn skip_any<I: Input>(input: &mut Pear<I>) -> Result<(), I> {
 
     match skipped {
         true => Ok(()),
-        false => return parse_error!(Expected::Token(None, None)),
+        false => return parse_expected!(exhausted(input, 1, Expected::Token(None, None))),
     }
 }
 
 /// Eats the current slice if it is `slice`.
 #[parser(raw)]
-pub fn eat_slice<I, S>(input: &mut Pear<I>, slice: S) -> Result<I::Slice, I>
+pub fn eat_slice<I, S, D>(input: &mut Pear<I, D>, slice: S) -> Result<I::Slice, I>
     where I: Input, S: Slice<I>
 {
     match input.eat_slice(slice.len(), |s| &slice == s) {
         Some(slice) => Ok(slice),
-        None => return parse_error!(expected_slice(input, slice))
+        None => return parse_expected!(expected_slice(input, slice))
     }
 }
 
 /// Succeeds if the current token is `token`.
 #[parser(raw)]
-pub fn peek<I, T>(input: &mut Pear<I>, token: T) -> Result<(), I>
+pub fn peek<I, T, D>(input: &mut Pear<I, D>, token: T) -> Result<(), I>
     where I: Input, T: Token<I>
 {
     match input.peek(|t| &token == t) {
         true => Ok(()),
-        false => return parse_error!(expected_token(input, Some(token)))
+        false => return parse_expected!(expected_token(input, Some(token)))
     }
 }
 
 /// Succeeds if `cond` holds for the current token.
 #[parser(raw)]
-pub fn peek_if_copy<I, F>(input: &mut Pear<I>, cond: F) -> Result<I::Token, I>
+pub fn peek_if_copy<I, F, D>(input: &mut Pear<I, D>, cond: F) -> Result<I::Token, I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
     match input.peek(cond) {
         true => Ok(input.token().unwrap()),
-        false => parse_error!(expected_token::<I::Token, _>(input, None))
+        false => parse_expected!(expected_token::<I::Token, _, _>(input, None))
     }
 }
 
 /// Succeeds if `cond` holds for the current token.
 #[parser(raw)]
-pub fn peek_if<I, F>(input: &mut Pear<I>, cond: F) -> Result<(), I>
+pub fn peek_if<I, F, D>(input: &mut Pear<I, D>, cond: F) -> Result<(), I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
     match input.peek(cond) {
         true => Ok(()),
-        false => parse_error!(expected_token::<I::Token, _>(input, None))
+        false => parse_expected!(expected_token::<I::Token, _, _>(input, None))
     }
 }
 
 /// Succeeds if the current slice is `slice`.
 #[parser(raw)]
-pub fn peek_slice<I, S>(input: &mut Pear<I>, slice: S) -> Result<(), I>
+pub fn peek_slice<I, S, D>(input: &mut Pear<I, D>, slice: S) -> Result<(), I>
     where I: Input, S: Slice<I>
 {
     match input.peek_slice(slice.len(), |s| &slice == s) {
         true => Ok(()),
-        false => return parse_error!(expected_slice(input, slice)),
+        false => return parse_expected!(expected_slice(input, slice)),
     }
 }
 
 /// Succeeds if the current slice is `slice`.
 #[parser(raw)]
-pub fn peek_slice_if<I, F>(input: &mut Pear<I>, len: usize, cond: F) -> Result<(), I>
+pub fn peek_slice_if<I, F, D>(input: &mut Pear<I, D>, len: usize, cond: F) -> Result<(), I>
     where I: Input, F: FnMut(&I::Slice) -> bool
 {
     match input.peek_slice(len, cond) {
         true => Ok(()),
-        false => return parse_error!(Expected::Slice(None, None)),
+        false => return parse_expected!(Expected::Slice(None, None)),
+    }
+}
+
+/// Marks an error's expected token or slice as having been matched without
+/// regard to case.
+fn ignoring_case<T, S>(expected: Expected<T, S>) -> Expected<T, S> {
+    match expected {
+        Expected::Token(Some(e), found) => {
+            Expected::Token(Some(crate::iformat!("{} (case-insensitive)", e)), found)
+        }
+        Expected::Slice(Some(e), found) => {
+            Expected::Slice(Some(crate::iformat!("{} (case-insensitive)", e)), found)
+        }
+        expected => expected,
+    }
+}
+
+/// Eats the current token if it is `token` without regard to ASCII case.
+/// Returns the token as it appears in the input.
+#[parser(raw)]
+pub fn eat_ignore_case<I, T, D>(input: &mut Pear<I, D>, token: T) -> Result<I::Token, I>
+    where I: Input, T: Token<I>, I::Token: IgnoreCase<T>
+{
+    match input.eat(|t| t.eq_ignore_ascii_case(&token)) {
+        Some(token) => Ok(token),
+        None => return parse_expected!(ignoring_case(expected_token(input, Some(token))))
+    }
+}
+
+/// Eats the current slice if it is `slice` without regard to ASCII case.
+/// Returns the slice as it appears in the input.
+#[parser(raw)]
+pub fn eat_slice_ignore_case<I, S, D>(input: &mut Pear<I, D>, slice: S) -> Result<I::Slice, I>
+    where I: Input, S: Slice<I>, I::Slice: IgnoreCase<S>
+{
+    match input.eat_slice(slice.len(), |s| s.eq_ignore_ascii_case(&slice)) {
+        Some(slice) => Ok(slice),
+        None => return parse_expected!(ignoring_case(expected_slice(input, slice)))
+    }
+}
+
+/// Succeeds if the current slice is `slice` without regard to ASCII case.
+#[parser(raw)]
+pub fn peek_slice_ignore_case<I, S, D>(input: &mut Pear<I, D>, slice: S) -> Result<(), I>
+    where I: Input, S: Slice<I>, I::Slice: IgnoreCase<S>
+{
+    match input.peek_slice(slice.len(), |s| s.eq_ignore_ascii_case(&slice)) {
+        true => Ok(()),
+        false => return parse_expected!(ignoring_case(expected_slice(input, slice))),
+    }
+}
+
+/// Returns `true` if `a` and `b` are equal without regard to Unicode case,
+/// that is, if their lowercase or their uppercase mappings are equal.
+fn eq_ignore_unicode_case(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase()) || a.to_uppercase().eq(b.to_uppercase())
+}
+
+/// Returns the length, in bytes, of the input that matches `slice` without
+/// regard to Unicode case, if any, without consuming it.
+fn unicode_case_match<I, D>(input: &mut Pear<I, D>, slice: &str) -> Option<usize>
+    where I: Input<Token = char> + Rewind
+{
+    let start = input.mark(&ParserInfo::new("unicode_case_match", true));
+    let len = slice.chars().try_fold(0, |len, c| {
+        input.eat(|&t| eq_ignore_unicode_case(t, c)).map(|t| len + t.len_utf8())
+    });
+
+    input.rewind_to(start);
+    input.unmark(start);
+    len
+}
+
+/// Like `expected_slice()`, but the slice found is as many `char`s long as
+/// `slice`, not as many bytes, since input that differs only in Unicode case
+/// may differ in length.
+fn expected_unicode_slice<I, S, D>(input: &mut Pear<I, D>, slice: S) -> Expected<I::Token, I::Slice>
+    where I: Input<Token = char> + Rewind, S: Slice<I> + AsRef<str>
+{
+    if !describe(input) {
+        return Expected::Elided;
+    }
+
+    let chars = slice.as_ref().chars().count();
+    let start = input.mark(&ParserInfo::new("expected_unicode_slice", true));
+    let (mut taken, mut len) = (0, 0);
+    while let Some(c) = input.eat(|_| taken < chars) {
+        taken += 1;
+        len += c.len_utf8();
+    }
+
+    input.rewind_to(start);
+    input.unmark(start);
+    match taken == chars {
+        true => Expected::slice(Some(&slice), input.slice(len)),
+        false => exhausted(input, chars - taken, Expected::slice(Some(&slice), None)),
+    }
+}
+
+/// Eats the current token if it is `token` without regard to Unicode case.
+/// Returns the token as it appears in the input.
+#[parser(raw)]
+pub fn eat_ignore_unicode_case<I, D>(input: &mut Pear<I, D>, token: char) -> Result<char, I>
+    where I: Input<Token = char>
+{
+    match input.eat(|&t| eq_ignore_unicode_case(t, token)) {
+        Some(token) => Ok(token),
+        None => return parse_expected!(ignoring_case(expected_token(input, Some(token))))
+    }
+}
+
+/// Eats the current slice if it is `slice` without regard to Unicode case.
+/// Returns the slice as it appears in the input, which may differ in length
+/// from `slice`. The input's slices must be measured in UTF-8 bytes, as they
+/// are for all of the `str`-based inputs in this crate.
+#[parser(raw)]
+pub fn eat_slice_ignore_unicode_case<I, S, D>(
+    input: &mut Pear<I, D>,
+    slice: S,
+) -> Result<I::Slice, I>
+    where I: Input<Token = char> + Rewind, S: Slice<I> + AsRef<str>
+{
+    let matched = unicode_case_match(input, slice.as_ref());
+    match matched.and_then(|n| input.eat_slice(n, |_| true)) {
+        Some(slice) => Ok(slice),
+        None => return parse_expected!(ignoring_case(expected_unicode_slice(input, slice)))
+    }
+}
+
+/// Succeeds if the current slice is `slice` without regard to Unicode case.
+#[parser(raw)]
+pub fn peek_slice_ignore_unicode_case<I, S, D>(input: &mut Pear<I, D>, slice: S) -> Result<(), I>
+    where I: Input<Token = char> + Rewind, S: Slice<I> + AsRef<str>
+{
+    match unicode_case_match(input, slice.as_ref()) {
+        Some(_) => Ok(()),
+        None => return parse_expected!(ignoring_case(expected_unicode_slice(input, slice))),
     }
 }
 
 /// Returns the current token.
 #[parser(raw)]
-pub fn peek_any<I: Input>(input: &mut Pear<I>) -> Result<I::Token, I> {
+pub fn peek_any<I: Input, D>(input: &mut Pear<I, D>) -> Result<I::Token, I> {
     match input.token() {
         Some(peeked) => Ok(peeked),
-        None => return parse_error!(Expected::Token(None, None)),
+        None => return parse_expected!(exhausted(input, 1, Expected::Token(None, None))),
     }
 }
 
 /// Skips tokens while `cond` matches.
 #[parser(raw)]
-pub fn skip_while<I, F>(input: &mut Pear<I>, cond: F) -> Result<usize, I>
+pub fn skip_while<I, F, D>(input: &mut Pear<I, D>, cond: F) -> Result<usize, I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
-    Ok(input.skip(cond))
+    let skipped = input.skip(cond);
+    if cut_short(input, 1) {
+        return parse_expected!(exhausted(input, 1, Expected::Token(None, None)));
+    }
+
+    Ok(skipped)
 }
 
 /// Consumes tokens while `cond` matches and returns them. Succeeds even if no
 /// tokens match.
 #[parser(raw)]
-pub fn take_while<I, F>(input: &mut Pear<I>, cond: F) -> Result<I::Many, I>
+pub fn take_while<I, F, D>(input: &mut Pear<I, D>, cond: F) -> Result<I::Many, I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
-    Ok(input.take(cond))
+    let value = input.take(cond);
+    if cut_short(input, 1) {
+        return parse_expected!(exhausted(input, 1, Expected::Token(None, None)));
+    }
+
+    Ok(value)
 }
 
 /// Consumes no tokens. Always succeeds. Equivalent to `take_while(|_| false)`.
 #[parser(raw)]
-pub fn none<I: Input>(input: &mut Pear<I>) -> Result<I::Many, I> {
-    take_while(input, |_| false)
+pub fn none<I: Input, D>(input: &mut Pear<I, D>) -> Result<I::Many, I> {
+    Ok(input.take(|_| false))
 }
 
 /// Consumes tokens while `cond` matches on a continously growing slice
 /// beginning at a length of `0` and ending when `cond` fails. Returns the slice
 /// between `0` and `cond` failing. Errors if no such slice exists.
 #[parser(raw)]
-pub fn take_while_slice<I, F>(input: &mut Pear<I>, mut f: F) -> Result<I::Slice, I>
+pub fn take_while_slice<I, F, D>(input: &mut Pear<I, D>, mut f: F) -> Result<I::Slice, I>
     where I: Input, F: FnMut(&I::Slice) -> bool
 {
     let mut len = 0;
@@ -210,9 +429,15 @@ pub fn take_while_slice<I, F>(input: &mut Pear<I>, mut f: F) -> Result<I::Slice,
         }
     }
 
+    // We ran out of slices, but the input may grow to make more available.
+    if input.slice(len).is_none() && cut_short(input, len + 1) {
+        let needed = missing(input, len + 1);
+        return parse_expected!(exhausted(input, needed, Expected::Slice(None, None)));
+    }
+
     match last_good {
         Some(len) => Ok(input.eat_slice(len, |_| true).expect("slice exists")),
-        None => return parse_error!(Expected::Slice(None, None)),
+        None => return parse_expected!(Expected::Slice(None, None)),
     }
 }
 
@@ -221,13 +446,20 @@ pub fn take_while_slice<I, F>(input: &mut Pear<I>, mut f: F) -> Result<I::Slice,
 /// given a string of "aaab" and a size 2 window predicate of `window == "aa"`,
 /// the return value is `"aa"` as the first failure to match is at `"ab"`.
 ///
-/// Always succeeds. If no tokens match, the result will be empty. If there are
-/// fewer than `n` tokens, takes all tokens and returns them.
+/// If no tokens match, the result will be empty. If there are fewer than `n`
+/// tokens, takes all tokens and returns them. Fails only if fewer than `n`
+/// tokens remain, at the start or past the last match, and the input can't say
+/// whether more follow: with [`Expected::Incomplete`] if the input is partial,
+/// [`Expected::Io`] if reading it failed, or [`Expected::Other`] if it ended at
+/// malformed data.
 #[parser(raw)]
-pub fn take_while_window<I, F>(input: &mut Pear<I>, n: usize, mut f: F) -> Result<I::Many, I>
+pub fn take_while_window<I, F, D>(input: &mut Pear<I, D>, n: usize, mut f: F) -> Result<I::Many, I>
     where I: Input + Rewind, F: FnMut(&I::Slice) -> bool
 {
-    if !input.has(n) {
+    if cut_short(input, n) {
+        let needed = missing(input, n);
+        return parse_expected!(exhausted(input, needed, Expected::Slice(None, None)));
+    } else if !input.has(n) {
         return Ok(input.take(|_| true));
     }
 
@@ -248,7 +480,14 @@ pub fn take_while_window<I, F>(input: &mut Pear<I>, n: usize, mut f: F) -> Resul
         }
     }
 
+    if cut_short(input, n) {
+        input.unmark(start);
+        let needed = missing(input, n);
+        return parse_expected!(exhausted(input, needed, Expected::Slice(None, None)));
+    }
+
     input.rewind_to(start);
+    input.unmark(start);
     Ok(input.take(|_| match tokens > 0 {
         true => { tokens -= 1; true },
         false => false
@@ -259,12 +498,12 @@ pub fn take_while_window<I, F>(input: &mut Pear<I>, n: usize, mut f: F) -> Resul
 /// returns them. Fails if there no tokens match, otherwise returns all of the
 /// tokens before the first failure.
 #[parser(raw)]
-pub fn take_some_while_window<I, F>(input: &mut Pear<I>, n: usize, f: F) -> Result<I::Many, I>
+pub fn take_some_while_window<I, F, D>(input: &mut Pear<I, D>, n: usize, f: F) -> Result<I::Many, I>
     where I: Input + Rewind, F: FnMut(&I::Slice) -> bool
 {
     let result = take_while_window(n, f)?;
     if result.is_empty() {
-        return parse_error!(Expected::Slice(None, None));
+        return parse_expected!(Expected::Slice(None, None));
     }
 
     Ok(result)
@@ -274,11 +513,11 @@ pub fn take_some_while_window<I, F>(input: &mut Pear<I>, n: usize, f: F) -> Resu
 /// returns them. Fails if there aren't at least `n` tokens, otherwise always
 /// otherwise always succeeds. If no tokens match, the result will be empty.
 #[parser(raw)]
-pub fn take_while_some_window<I, F>(input: &mut Pear<I>, n: usize, f: F) -> Result<I::Many, I>
+pub fn take_while_some_window<I, F, D>(input: &mut Pear<I, D>, n: usize, f: F) -> Result<I::Many, I>
     where I: Input + Rewind, F: FnMut(&I::Slice) -> bool
 {
     if !input.has(n) {
-        return parse_error!(Expected::Slice(None, None));
+        return parse_expected!(Expected::Slice(None, None));
     }
 
     take_while_window(input, n, f)
@@ -288,11 +527,15 @@ pub fn take_while_some_window<I, F>(input: &mut Pear<I>, n: usize, f: F) -> Resu
 /// returns them. Fails if there aren't at least `n` tokens or if no tokens
 /// match, otherwise returns all of the tokens before the first failure.
 #[parser(raw)]
-pub fn take_some_while_some_window<I, F>(input: &mut Pear<I>, n: usize, f: F) -> Result<I::Many, I>
+pub fn take_some_while_some_window<I, F, D>(
+    input: &mut Pear<I, D>,
+    n: usize,
+    f: F,
+) -> Result<I::Many, I>
     where I: Input + Rewind, F: FnMut(&I::Slice) -> bool
 {
     if !input.has(n) {
-        return parse_error!(Expected::Slice(None, None));
+        return parse_expected!(Expected::Slice(None, None));
     }
 
     take_some_while_window(input, n, f)
@@ -301,7 +544,7 @@ pub fn take_some_while_some_window<I, F>(input: &mut Pear<I>, n: usize, f: F) ->
 /// Consumes tokens while `cond` matches on a window of tokens of size `n` and
 /// returns them. Succeeds even if no tokens match.
 #[parser(raw)]
-pub fn take_until_slice<I, S>(input: &mut Pear<I>, slice: S) -> Result<I::Many, I>
+pub fn take_until_slice<I, S, D>(input: &mut Pear<I, D>, slice: S) -> Result<I::Many, I>
     where I: Input + Rewind, S: Slice<I>
 {
     take_while_window(input, slice.len(), |s| &slice != s)
@@ -310,12 +553,17 @@ pub fn take_until_slice<I, S>(input: &mut Pear<I>, slice: S) -> Result<I::Many,
 /// Consumes tokens while `cond` matches and returns them. Succeeds only if at
 /// least one token matched `cond`.
 #[parser(raw)]
-pub fn take_some_while<I, F>(input: &mut Pear<I>, cond: F) -> Result<I::Many, I>
+pub fn take_some_while<I, F, D>(input: &mut Pear<I, D>, cond: F) -> Result<I::Many, I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
     let value = input.take(cond);
-    if value.len() == 0 {
-        return parse_error!(Expected::Token(None, None));
+    if value.len() == 0 || cut_short(input, 1) {
+        let expected = match input.has(1) {
+            true => Expected::Token(None, None),
+            false => exhausted(input, 1, Expected::Token(None, None)),
+        };
+
+        return parse_expected!(expected);
     }
 
     Ok(value)
@@ -324,8 +572,8 @@ pub fn take_some_while<I, F>(input: &mut Pear<I>, cond: F) -> Result<I::Many, I>
 /// Consumes tokens while `cond` matches and the token is not `until`. Succeeds
 /// even if no tokens match.
 #[parser(raw)]
-pub fn take_while_until<I, T, F>(
-    input: &mut Pear<I>,
+pub fn take_while_until<I, T, F, D>(
+    input: &mut Pear<I, D>,
     mut cond: F,
     until: T,
 ) -> Result<I::Many, I>
@@ -339,8 +587,8 @@ pub fn take_while_until<I, T, F>(
 /// Consumes tokens while `cond` matches and the token is not `until`. Succeeds
 /// only if at least one token matched `cond`.
 #[parser(raw)]
-pub fn take_some_while_until<I, T, F>(
-    input: &mut Pear<I>,
+pub fn take_some_while_until<I, T, F, D>(
+    input: &mut Pear<I, D>,
     mut cond: F,
     until: T,
 ) -> Result<I::Many, I>
@@ -353,29 +601,45 @@ pub fn take_some_while_until<I, T, F>(
 
 /// Takes at most `n` tokens.
 #[parser(raw)]
-pub fn take_n<I: Input>(input: &mut Pear<I>, n: usize) -> Result<I::Many, I> {
+pub fn take_n<I: Input, D>(input: &mut Pear<I, D>, n: usize) -> Result<I::Many, I> {
     let mut i = 0;
-    Ok(input.take(|_| { let c = i < n; i += 1; c }))
+    let value = input.take(|_| { let c = i < n; i += 1; c });
+    if value.len() < n && cut_short(input, 1) {
+        let needed = n - value.len();
+        return parse_expected!(exhausted(input, needed, Expected::Token(None, None)));
+    }
+
+    Ok(value)
 }
 
 /// Takes at most `n` tokens as long as `cond` holds.
 #[parser(raw)]
-pub fn take_n_while<I, F>(input: &mut Pear<I>, n: usize, mut cond: F) -> Result<I::Many, I>
+pub fn take_n_while<I, F, D>(input: &mut Pear<I, D>, n: usize, mut cond: F) -> Result<I::Many, I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
     let mut i = 0;
-    Ok(input.take(|c| { cond(c) && { let ok = i < n; i += 1; ok } }))
+    let value = input.take(|c| { cond(c) && { let ok = i < n; i += 1; ok } });
+    if value.len() < n && cut_short(input, 1) {
+        return parse_expected!(exhausted(input, 1, Expected::Token(None, None)));
+    }
+
+    Ok(value)
 }
 
 /// Take exactly `n` tokens, ensuring `cond` holds on all `n`.
 #[parser(raw)]
-pub fn take_n_if<I, F>(input: &mut Pear<I>, n: usize, mut cond: F) -> Result<I::Many, I>
+pub fn take_n_if<I, F, D>(input: &mut Pear<I, D>, n: usize, mut cond: F) -> Result<I::Many, I>
     where I: Input, F: FnMut(&I::Token) -> bool
 {
     let mut i = 0;
     let v = input.take(|c| { cond(c) && { let ok = i < n; i += 1; ok } });
     if v.len() != n {
-        return parse_error!(Expected::Token(None, None));
+        let expected = match cut_short(input, 1) {
+            true => exhausted(input, n - v.len(), Expected::Token(None, None)),
+            false => Expected::Token(None, None),
+        };
+
+        return parse_expected!(expected);
     }
 
     Ok(v)
@@ -385,8 +649,8 @@ pub fn take_n_if<I, F>(input: &mut Pear<I>, n: usize, mut cond: F) -> Result<I::
 /// all of the tokens in between. The tokens in between must match `cond`.
 /// Succeeds even if there are no tokens between `start` and `end`.
 #[parser(raw)]
-pub fn delimited<I, T, F>(
-    input: &mut Pear<I>,
+pub fn delimited<I, T, F, D>(
+    input: &mut Pear<I, D>,
     start: T,
     mut cond: F,
     end: T,
@@ -405,8 +669,8 @@ pub fn delimited<I, T, F>(
 /// all of the tokens in between. The tokens in between must match `cond`. There
 /// must be at least one token between `start` and `end`.
 #[parser(raw)]
-pub fn delimited_some<I, T, F>(
-    input: &mut Pear<I>,
+pub fn delimited_some<I, T, F, D>(
+    input: &mut Pear<I, D>,
     start: T,
     mut cond: F,
     end: T,
@@ -423,17 +687,23 @@ pub fn delimited_some<I, T, F>(
 
 /// Succeeds only if the input has reached EOF.
 #[parser(raw)]
-pub fn eof<I: Input>(input: &mut Pear<I>) -> Result<(), I> {
-    Ok(if input.has(1) {
+pub fn eof<I: Input, D>(input: &mut Pear<I, D>) -> Result<(), I> {
+    if input.has(1) {
         let next = input.token();
-        parse_error!(Expected::Eof(next))?
-    })
+        parse_expected!(Expected::Eof(next))?
+    }
+
+    if cut_short(input, 1) {
+        parse_expected!(exhausted(input, 1, Expected::Eof(None)))?
+    }
+
+    Ok(())
 }
 
 /// Like `delimited` but keeps the `start` and `end`.
 #[parser(raw)]
-pub fn enclosed<I, T, F>(
-    input: &mut Pear<I>,
+pub fn enclosed<I, T, F, D>(
+    input: &mut Pear<I, D>,
     start: T,
     mut cond: F,
     end: T,
@@ -461,8 +731,8 @@ pub fn enclosed<I, T, F>(
     });
 
     match state {
-        State::Start => parse_error!(expected_token(input, Some(start))),
-        State::Inner => parse_error!(expected_token(input, Some(end))),
+        State::Start => parse_expected!(expected_token(input, Some(start))),
+        State::Inner => parse_expected!(expected_token(input, Some(end))),
         State::End => Ok(value)
     }
 }
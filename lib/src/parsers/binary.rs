@@ -0,0 +1,166 @@
+//! Parsers for binary encodings of integers and floats.
+//!
+//! All parsers work on any input whose tokens are bytes, including
+//! [`Bytes`](crate::input::Bytes), [`ReaderInput`](crate::input::ReaderInput),
+//! and `Cursor<&[u8]>`. Fixed-width parsers fail with
+//! [`Expected::Slice`] naming the value they expected if fewer bytes than its
+//! width remain. Varint parsers fail with [`Expected::Token`] if the input ends
+//! mid-varint and with [`Expected::Other`] if the encoded value overflows.
+//!
+//! # Example
+//!
+//! ```rust
+//! use pear::input::{Pear, Cursor};
+//! use pear::parsers::binary::*;
+//!
+//! let mut input = Pear::from(Cursor::from(&[0x01, 0x02, 0x02, 0x01, 0xac, 0x02][..]));
+//! assert_eq!(be_u16(&mut input).unwrap(), 0x0102);
+//! assert_eq!(le_u16(&mut input).unwrap(), 0x0102);
+//! assert_eq!(varint(&mut input).unwrap(), 300);
+//! ```
+
+use crate::error::Expected;
+use crate::input::{Input, Pear, Result};
+use crate::macros::parser;
+use crate::parsers::{exhausted, missing};
+
+/// Fills `bytes` with the next `bytes.len()` tokens. `what` names the value
+/// being parsed in the error if there aren't enough tokens.
+#[parser(raw)]
+fn fixed<I, D>(input: &mut Pear<I, D>, bytes: &mut [u8], what: &'static str) -> Result<(), I>
+    where I: Input<Token = u8>
+{
+    if !input.has(bytes.len()) {
+        let needed = missing(input, bytes.len());
+        let expected = Expected::Slice(Some(what.into()), None);
+        return parse_error!(exhausted(input, needed, expected));
+    }
+
+    for byte in bytes.iter_mut() {
+        *byte = input.eat(|_| true).expect("has(n) => n tokens");
+    }
+
+    Ok(())
+}
+
+macro_rules! fixed_width {
+    ($($name:ident: $T:ty = $from:ident, $what:literal;)*) => ($(
+        #[doc = concat!("Parses a ", $what, ".")]
+        #[parser(raw)]
+        pub fn $name<I: Input<Token = u8>, D>(input: &mut Pear<I, D>) -> Result<$T, I> {
+            let mut bytes = [0; std::mem::size_of::<$T>()];
+            fixed(&mut bytes, $what)?;
+            Ok(<$T>::$from(bytes))
+        }
+    )*)
+}
+
+fixed_width! {
+    be_u16: u16 = from_be_bytes, "big-endian u16";
+    be_u32: u32 = from_be_bytes, "big-endian u32";
+    be_u64: u64 = from_be_bytes, "big-endian u64";
+    be_u128: u128 = from_be_bytes, "big-endian u128";
+    be_i16: i16 = from_be_bytes, "big-endian i16";
+    be_i32: i32 = from_be_bytes, "big-endian i32";
+    be_i64: i64 = from_be_bytes, "big-endian i64";
+    be_i128: i128 = from_be_bytes, "big-endian i128";
+    be_f32: f32 = from_be_bytes, "big-endian f32";
+    be_f64: f64 = from_be_bytes, "big-endian f64";
+
+    le_u16: u16 = from_le_bytes, "little-endian u16";
+    le_u32: u32 = from_le_bytes, "little-endian u32";
+    le_u64: u64 = from_le_bytes, "little-endian u64";
+    le_u128: u128 = from_le_bytes, "little-endian u128";
+    le_i16: i16 = from_le_bytes, "little-endian i16";
+    le_i32: i32 = from_le_bytes, "little-endian i32";
+    le_i64: i64 = from_le_bytes, "little-endian i64";
+    le_i128: i128 = from_le_bytes, "little-endian i128";
+    le_f32: f32 = from_le_bytes, "little-endian f32";
+    le_f64: f64 = from_le_bytes, "little-endian f64";
+}
+
+/// Parses an unsigned LEB128 varint of at most `bits` bits. If `signed`, the
+/// value is sign-extended from the last byte's sign bit as in signed LEB128.
+#[parser(raw)]
+fn leb128<I, D>(input: &mut Pear<I, D>, bits: u32, signed: bool) -> Result<u64, I>
+    where I: Input<Token = u8>
+{
+    let (mut value, mut shift) = (0u64, 0u32);
+    loop {
+        let byte = match input.eat(|_| true) {
+            Some(byte) => byte,
+            None => {
+                let expected = Expected::Token(Some("varint byte".into()), None);
+                return parse_error!(exhausted(input, 1, expected));
+            }
+        };
+
+        // Check that the payload, and for signed values its sign extension,
+        // fits in the remaining `bits - shift` bits.
+        let payload = u64::from(byte & 0x7f);
+        let fits = match bits - shift {
+            width if width >= 7 => true,
+            width if !signed => payload >> width == 0,
+            width => {
+                let sign = (payload >> (width - 1)) & 1;
+                payload >> width == (0x7f >> width) * sign
+            }
+        };
+
+        if !fits || (byte & 0x80 != 0 && bits - shift <= 7) {
+            return parse_error!(Expected::from(match (signed, bits) {
+                (false, 32) => "varint overflows u32",
+                (false, _) => "varint overflows u64",
+                (true, 32) => "varint overflows i32",
+                (true, _) => "varint overflows i64",
+            }));
+        }
+
+        value |= payload << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            if signed && shift < 64 && byte & 0x40 != 0 {
+                value |= !0 << shift;
+            }
+
+            return Ok(value);
+        }
+    }
+}
+
+/// Parses an unsigned LEB128 varint, as used by Protocol Buffers, into a
+/// `u64`. Fails if the encoded value doesn't fit.
+#[parser(raw)]
+pub fn varint<I: Input<Token = u8>, D>(input: &mut Pear<I, D>) -> Result<u64, I> {
+    Ok(leb128(64, false)?)
+}
+
+/// Parses an unsigned LEB128 varint into a `u32`. Fails if the encoded value
+/// doesn't fit.
+#[parser(raw)]
+pub fn varint32<I: Input<Token = u8>, D>(input: &mut Pear<I, D>) -> Result<u32, I> {
+    Ok(leb128(32, false)? as u32)
+}
+
+/// Parses a signed LEB128 varint, as used by DWARF and WebAssembly, into an
+/// `i64`. Fails if the encoded value doesn't fit.
+#[parser(raw)]
+pub fn sleb128<I: Input<Token = u8>, D>(input: &mut Pear<I, D>) -> Result<i64, I> {
+    Ok(leb128(64, true)? as i64)
+}
+
+/// Parses a zigzag-encoded varint, as used by Protocol Buffers' `sint64`,
+/// into an `i64`.
+#[parser(raw)]
+pub fn zigzag<I: Input<Token = u8>, D>(input: &mut Pear<I, D>) -> Result<i64, I> {
+    let n = leb128(64, false)?;
+    Ok((n >> 1) as i64 ^ -((n & 1) as i64))
+}
+
+/// Parses a zigzag-encoded varint, as used by Protocol Buffers' `sint32`,
+/// into an `i32`.
+#[parser(raw)]
+pub fn zigzag32<I: Input<Token = u8>, D>(input: &mut Pear<I, D>) -> Result<i32, I> {
+    let n = leb128(32, false)? as u32;
+    Ok((n >> 1) as i32 ^ -((n & 1) as i32))
+}
@@ -0,0 +1,55 @@
+//! Parsers for bit-packed data read from an input of `bool` tokens such as
+//! [`Bits`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use pear::input::{Pear, Bits};
+//! use pear::parsers::{eat_any, bits::*};
+//!
+//! // A 3-bit version, a flag, then a byte-aligned 8-bit length.
+//! let mut input: Pear<Bits> = Pear::new(&[0b101_1_0000, 42]);
+//! assert_eq!(take_bits(&mut input, 3).unwrap(), 0b101);
+//! assert_eq!(eat_any(&mut input).unwrap(), true);
+//! assert_eq!(align_to_byte(&mut input).unwrap(), 4);
+//! assert_eq!(take_bits(&mut input, 8).unwrap(), 42);
+//! ```
+
+use crate::error::Expected;
+use crate::input::{Input, Pear, Result, Bits};
+use crate::macros::parser;
+use crate::parsers::{exhausted, missing};
+
+/// Takes the next `n` bits, returning them as an unsigned integer with the
+/// first bit taken as the most significant. Fails without consuming anything
+/// if fewer than `n` bits remain.
+///
+/// # Panics
+///
+/// Panics if `n` is greater than 64.
+#[parser(raw)]
+pub fn take_bits<I, D>(input: &mut Pear<I, D>, n: usize) -> Result<u64, I>
+    where I: Input<Token = bool>
+{
+    assert!(n <= 64, "take_bits: {} bits don't fit in a u64", n);
+    if !input.has(n) {
+        let needed = missing(input, n);
+        let expected = Expected::Slice(Some(crate::iformat!("{} bits", n)), None);
+        return parse_error!(exhausted(input, needed, expected));
+    }
+
+    let mut value = 0;
+    for _ in 0..n {
+        let bit = input.eat(|_| true).expect("has(n) => n tokens");
+        value = (value << 1) | u64::from(bit);
+    }
+
+    Ok(value)
+}
+
+/// Skips to the next byte boundary, if not already at one. Returns the number
+/// of bits skipped. Never fails.
+#[parser(raw)]
+pub fn align_to_byte<'a, D>(input: &mut Pear<Bits<'a>, D>) -> Result<usize, Bits<'a>> {
+    Ok(input.align())
+}
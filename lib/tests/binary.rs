@@ -0,0 +1,102 @@
+use pear::input::{Pear, Bytes, Cursor, Partial};
+use pear::error::Expected;
+use pear::parsers::binary::*;
+use pear::parsers::eof;
+
+fn bytes(bytes: &[u8]) -> Pear<Bytes<'_>> {
+    Pear::new(bytes)
+}
+
+#[test]
+fn test_fixed_width() {
+    let mut input = bytes(b"\x12\x34\x56\x78\x12\x34\x56\x78\xff\xfe\xfe\xff");
+    assert_eq!(be_u32(&mut input).unwrap(), 0x12345678);
+    assert_eq!(le_u32(&mut input).unwrap(), 0x78563412);
+    assert_eq!(be_i16(&mut input).unwrap(), -2);
+    assert_eq!(le_i16(&mut input).unwrap(), -2);
+    assert!(eof(&mut input).is_ok());
+
+    let mut input = bytes(&[0x40, 0x49, 0x0f, 0xdb, 0, 0, 0, 0, 0, 0, 0xf0, 0x3f]);
+    assert_eq!(be_f32(&mut input).unwrap(), std::f32::consts::PI);
+    assert_eq!(le_f64(&mut input).unwrap(), 1.0);
+
+    let mut input = bytes(&[0xff; 16]);
+    assert_eq!(be_u128(&mut input).unwrap(), u128::MAX);
+}
+
+#[test]
+fn test_cursor() {
+    let mut input = Pear::from(Cursor::from(&[0x00, 0x01, 0x96, 0x01][..]));
+    assert_eq!(be_u16(&mut input).unwrap(), 1);
+    assert_eq!(varint(&mut input).unwrap(), 150);
+}
+
+#[test]
+fn test_fixed_width_truncated() {
+    let mut input = bytes(b"\x01\x02\x03");
+    let err = be_u64(&mut input).unwrap_err();
+    assert_eq!(err.to_string().lines().next().unwrap(),
+        "unexpected EOF: expected slice big-endian u64 (1:1 [EOF])");
+    assert!(matches!(err.error, Expected::Slice(Some(ref s), None) if s == "big-endian u64"));
+
+    // Nothing was consumed.
+    assert_eq!(be_u16(&mut input).unwrap(), 0x0102);
+
+    let mut input = Pear::from(Partial::new(Bytes::from(b"\x01\x02\x03")));
+    assert!(matches!(le_u64(&mut input).unwrap_err().error, Expected::Incomplete(5)));
+}
+
+#[test]
+fn test_varints() {
+    let mut input = bytes(b"\x00\x7f\xac\x02\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01");
+    assert_eq!(varint(&mut input).unwrap(), 0);
+    assert_eq!(varint(&mut input).unwrap(), 127);
+    assert_eq!(varint(&mut input).unwrap(), 300);
+    assert_eq!(varint(&mut input).unwrap(), u64::MAX);
+
+    let mut input = bytes(b"\xff\xff\xff\xff\x0f");
+    assert_eq!(varint32(&mut input).unwrap(), u32::MAX);
+
+    let mut input = bytes(b"\x02\xc0\xbb\x78\x7f\x80\x80\x80\x80\x80\x80\x80\x80\x80\x7f");
+    assert_eq!(sleb128(&mut input).unwrap(), 2);
+    assert_eq!(sleb128(&mut input).unwrap(), -123456);
+    assert_eq!(sleb128(&mut input).unwrap(), -1);
+    assert_eq!(sleb128(&mut input).unwrap(), i64::MIN);
+
+    let mut input = bytes(b"\x00\x01\x02\x03\xfe\xff\xff\xff\x0f\xff\xff\xff\xff\x0f");
+    assert_eq!(zigzag(&mut input).unwrap(), 0);
+    assert_eq!(zigzag(&mut input).unwrap(), -1);
+    assert_eq!(zigzag(&mut input).unwrap(), 1);
+    assert_eq!(zigzag(&mut input).unwrap(), -2);
+    assert_eq!(zigzag32(&mut input).unwrap(), i32::MAX);
+    assert_eq!(zigzag32(&mut input).unwrap(), i32::MIN);
+}
+
+fn error<T>(result: pear::input::Result<T, Bytes<'_>>) -> String {
+    result.map(|_| ()).unwrap_err().error.to_string()
+}
+
+#[test]
+fn test_varint_overflow() {
+    let u64_max_plus_one = b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x02";
+    assert_eq!(error(varint(&mut bytes(u64_max_plus_one))), "varint overflows u64");
+
+    let too_long = b"\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x00";
+    assert_eq!(error(varint(&mut bytes(too_long))), "varint overflows u64");
+
+    let u32_max_plus_one = b"\x80\x80\x80\x80\x10";
+    assert_eq!(error(varint32(&mut bytes(u32_max_plus_one))), "varint overflows u32");
+    assert_eq!(error(varint32(&mut bytes(b"\x80\x80\x80\x80\x80\x00"))), "varint overflows u32");
+
+    let i64_max_plus_one = b"\x80\x80\x80\x80\x80\x80\x80\x80\x80\x01";
+    assert_eq!(error(sleb128(&mut bytes(i64_max_plus_one))), "varint overflows i64");
+}
+
+#[test]
+fn test_varint_truncated() {
+    let err = varint(&mut bytes(b"\xac")).unwrap_err();
+    assert!(matches!(err.error, Expected::Token(Some(ref s), None) if s == "varint byte"));
+
+    let mut input = Pear::from(Partial::new(Bytes::from(b"\xff\xff")));
+    assert!(matches!(varint(&mut input).unwrap_err().error, Expected::Incomplete(1)));
+}
@@ -0,0 +1,85 @@
+// Binary literals are grouped by field, not by nibble.
+#![allow(clippy::unusual_byte_groupings)]
+
+use pear::input::{Pear, Bits, BitSpan, BitOffset, Length};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*, combinators::*};
+use pear::parsers::bits::*;
+
+type Input<'a> = Pear<Bits<'a>>;
+type Result<'a, T> = pear::input::Result<T, Bits<'a>>;
+
+#[derive(Debug, PartialEq)]
+struct Header {
+    version: u64,
+    flag: bool,
+    kind: u64,
+    length: u64,
+}
+
+#[parser]
+fn header<'a>(input: &mut Input<'a>) -> Result<'a, Header> {
+    let version = take_bits(3)?;
+    let flag = eat_any()?;
+    let kind = take_bits(2)?;
+    align_to_byte()?;
+    let length = take_bits(12)?;
+    align_to_byte()?;
+    Header { version, flag, kind, length }
+}
+
+#[parser(rewind)]
+fn marker_bits<'a>(input: &mut Input<'a>) -> Result<'a, ()> {
+    eat(true)?;
+    eat(true)?;
+    eat(true)?;
+}
+
+#[parser]
+fn framed<'a>(input: &mut Input<'a>) -> Result<'a, (bool, BitSpan)> {
+    take_bits(5)?;
+    let found = succeeds(input, marker_bits);
+    let span = parse_context!();
+    align_to_byte()?;
+    (found, span)
+}
+
+#[test]
+fn test_bits_header() {
+    let header = parse!(header: Bits::from(&[0b011_1_10_01, 0x12, 0x30])).unwrap();
+    assert_eq!(header, Header { version: 3, flag: true, kind: 2, length: 0x123 });
+}
+
+#[test]
+fn test_bits_rewind() {
+    let (found, span) = parse!(framed: Bits::from(&[0b00000_110])).unwrap();
+    assert!(!found);
+    assert_eq!(span.end, BitOffset { byte: 0, bit: 5 });
+    assert_eq!(span.cursor, Some(true));
+
+    let (found, span) = parse!(framed: Bits::from(&[0b00000_111])).unwrap();
+    assert!(found);
+    assert_eq!(span.end, BitOffset { byte: 1, bit: 0 });
+}
+
+#[test]
+fn test_bits_truncated() {
+    let err = parse!(header: Bits::from(&[0b011_1_10_01, 0x12])).unwrap_err();
+    assert!(matches!(err.error, Expected::Slice(Some(ref s), None) if s == "12 bits"));
+    assert_eq!(err.info.context.start, BitOffset { byte: 1, bit: 0 });
+
+    let string = err.to_string();
+    assert!(string.contains("+ header byte 0 bit 0 to byte 1 bit 0 [0]"), "{}", string);
+}
+
+#[test]
+fn test_bits_slices() {
+    let mut input = Pear::<Bits<'_>>::new(&[0b1111_0000, 0b1010_0000]);
+    let ones = take_while(&mut input, |&b| b).unwrap();
+    assert_eq!((ones.len(), ones.to_u64()), (4, Some(0b1111)));
+
+    let rest = take_n(&mut input, 8).unwrap();
+    assert_eq!(rest.to_u64(), Some(0b0000_1010));
+    assert_eq!(rest.iter().filter(|&b| b).count(), 2);
+    assert_eq!(input.offset(), BitOffset { byte: 1, bit: 4 });
+}
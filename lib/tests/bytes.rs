@@ -0,0 +1,67 @@
+use pear::input::{ByteSpan, Bytes, Input as _, Pear};
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Pear<Bytes<'a>>;
+type Result<'a, T> = pear::input::Result<T, Bytes<'a>>;
+
+#[parser]
+fn header<'a>(input: &mut Input<'a>) -> Result<'a, (&'a [u8], &'a [u8])> {
+    let name = take_some_while_until(|&c| c.is_ascii_alphanumeric() || c == b'-', b':')?;
+    eat(b':')?;
+    skip_while(|&c| c == b' ')?;
+    let value = take_while(|&c| c != b'\r')?;
+    eat_slice(b"\r\n")?;
+    (name, value)
+}
+
+#[parser(rewind)]
+fn rewinding_header<'a>(input: &mut Input<'a>) -> Result<'a, (&'a [u8], &'a [u8])> {
+    header()?
+}
+
+#[parser]
+fn two_headers<'a>(input: &mut Input<'a>) -> Result<'a, ByteSpan<'a>> {
+    header()?;
+    parse_mark!();
+    header()?;
+    parse_context!()
+}
+
+#[test]
+fn test_bytes_header() {
+    let (name, value) = parse!(header: Bytes::from("Host: example.com\r\n")).unwrap();
+    assert_eq!(name, b"Host");
+    assert_eq!(value, b"example.com");
+}
+
+#[test]
+fn test_bytes_context() {
+    let span = parse!(two_headers: Bytes::from(b"A: b\r\nCd: e\r\n")).unwrap();
+    assert_eq!(span, ByteSpan {
+        start: (2, 1, 6),
+        end: (3, 1, 13),
+        snippet: Some(b"Cd: e\r\n"),
+        cursor: None,
+    });
+}
+
+#[test]
+fn test_bytes_rewind() {
+    let mut input = Input::new(b"A: b\r\nC\xffd: e\r\n");
+    assert!(rewinding_header(&mut input).is_ok());
+    assert!(rewinding_header(&mut input).is_err());
+    assert_eq!(input.token(), Some(b'C'));
+}
+
+#[test]
+fn test_bytes_error_display() {
+    let err = parse!(header: Bytes::from(b"Host: a\x01\xfe\n")).unwrap_err();
+    let header = &err.stack.last().unwrap().context;
+    assert_eq!(header.start, (1, 1, 0));
+    assert_eq!(header.end, (2, 1, 10));
+    assert_eq!(header.snippet, Some(&b"Host: a\x01\xfe\n"[..]));
+    assert_eq!(header.cursor, None);
+
+    let string = err.to_string();
+    assert!(string.contains(r#"1:1 to 2:1 "Host: a\x01\xfe\n""#), "{}", string);
+}
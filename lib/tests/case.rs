@@ -0,0 +1,89 @@
+use pear::input::{Pear, Text, Cursor, Bytes};
+use pear::{macros::*, parsers::*};
+
+#[derive(Debug, PartialEq)]
+enum Method { Get, Post }
+
+type ByteInput<'a> = Pear<Cursor<&'a [u8]>>;
+
+#[parser]
+fn method<'a>(input: &mut ByteInput<'a>) -> pear::input::Result<Method, Cursor<&'a [u8]>> {
+    switch! {
+        eat_slice_ignore_case(b"GET") => Method::Get,
+        eat_slice_ignore_case(b"POST") => Method::Post,
+        _ => parse_error!("unknown method")?
+    }
+}
+
+#[test]
+fn test_ascii_byte_cursor() {
+    assert_eq!(parse!(method: Cursor::from(&b"get"[..])).unwrap(), Method::Get);
+    assert_eq!(parse!(method: Cursor::from(&b"PoSt"[..])).unwrap(), Method::Post);
+    assert!(parse!(method: Cursor::from(&b"PUT"[..])).is_err());
+
+    let mut input = Pear::from(Cursor::from(&b"Content-TYPE:"[..]));
+    let name = eat_slice_ignore_case(&mut input, b"content-type").unwrap();
+    assert_eq!(name.values, b"Content-TYPE");
+    assert_eq!(eat_ignore_case(&mut input, b':').unwrap(), b':');
+}
+
+#[test]
+fn test_ascii_text() {
+    let mut input = Pear::from(Text::from("SELECT x fRoM t"));
+    assert_eq!(eat_slice_ignore_case(&mut input, "select").unwrap(), "SELECT");
+    assert_eq!(eat_ignore_case(&mut input, ' ').unwrap(), ' ');
+    assert_eq!(eat_ignore_case(&mut input, 'X').unwrap(), 'x');
+    assert!(peek_slice_ignore_case(&mut input, " FROM").is_ok());
+    assert_eq!(eat_slice_ignore_case(&mut input, " from").unwrap(), " fRoM");
+
+    let mut input = Pear::from(Bytes::from("Text/HTML"));
+    assert_eq!(eat_slice_ignore_case(&mut input, &b"text/html"[..]).unwrap(), b"Text/HTML");
+
+    let mut input: Pear<&str> = Pear::new("Yes");
+    assert_eq!(eat_slice_ignore_case(&mut input, "yES").unwrap(), "Yes");
+}
+
+#[test]
+fn test_ascii_ignores_unicode_case() {
+    let mut input = Pear::from(Text::from("ÉTÉ"));
+    assert!(eat_slice_ignore_case(&mut input, "été").is_err());
+    assert!(eat_ignore_case(&mut input, 'é').is_err());
+}
+
+#[test]
+fn test_unicode() {
+    let mut input = Pear::from(Text::from("ÉTÉ Straße ΣΊΣΥΦΟΣ"));
+    assert_eq!(eat_slice_ignore_unicode_case(&mut input, "été").unwrap(), "ÉTÉ");
+    assert_eq!(eat_ignore_unicode_case(&mut input, ' ').unwrap(), ' ');
+    assert_eq!(eat_slice_ignore_unicode_case(&mut input, "STRAßE").unwrap(), "Straße");
+    assert!(peek_slice_ignore_unicode_case(&mut input, " σίσυφος").is_ok());
+    assert_eq!(eat_slice_ignore_unicode_case(&mut input, " σίσυφος").unwrap(), " ΣΊΣΥΦΟΣ");
+    assert!(eof(&mut input).is_ok());
+
+    // The Kelvin sign is three bytes long; its lowercase form is ASCII 'k'.
+    let mut input = Pear::from(Text::from("\u{212A}m"));
+    assert_eq!(eat_slice_ignore_unicode_case(&mut input, "km").unwrap(), "\u{212A}m");
+}
+
+#[test]
+fn test_errors_say_case_insensitive() {
+    let mut input = Pear::from(Text::from("PUT /"));
+    let err = eat_slice_ignore_case(&mut input, "get").unwrap_err();
+    assert_eq!(err.error.to_string(), "expected slice \"get\" (case-insensitive) but found \"PUT\"");
+
+    let err = eat_ignore_unicode_case(&mut input, 'é').unwrap_err();
+    assert!(err.to_string().starts_with("expected token 'é' (case-insensitive)"));
+
+    let mut input = Pear::from(Text::from("ab"));
+    let err = eat_slice_ignore_unicode_case(&mut input, "abc").unwrap_err();
+    assert!(err.to_string().contains("\"abc\" (case-insensitive)"));
+
+    // The slice found is as many `char`s long as the slice expected.
+    let mut input = Pear::from(Text::from("ÉCOLE!"));
+    let err = eat_slice_ignore_unicode_case(&mut input, "ecole").unwrap_err();
+    assert_eq!(err.error.to_string(),
+        "expected slice \"ecole\" (case-insensitive) but found \"ÉCOLE\"");
+
+    let err = peek_slice_ignore_unicode_case(&mut input, "écoles").unwrap_err();
+    assert!(err.error.to_string().ends_with("but found \"ÉCOLE!\""));
+}
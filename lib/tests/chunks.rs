@@ -0,0 +1,81 @@
+use std::borrow::Cow;
+
+use pear::input::{Pear, Chunks, Seek, Rewind, Input, ParserInfo};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Result<'a, T> = pear::input::Result<T, Chunks<'a, str>>;
+
+#[parser]
+fn ident<'a>(input: &mut Pear<Chunks<'a, str>>) -> Result<'a, Cow<'a, str>> {
+    take_some_while(|c| c.is_ascii_alphanumeric() || *c == '_')?
+}
+
+#[parser]
+fn idents<'a>(input: &mut Pear<Chunks<'a, str>>) -> Result<'a, Vec<Cow<'a, str>>> {
+    let idents: Vec<_> = series(ident, ',')?;
+    eof()?;
+    idents
+}
+
+#[test]
+fn test_chunks_borrow_within_chunk() {
+    let chunks = ["foo,bar,b", "az,", "", "quux"];
+    let idents = parse!(idents: Chunks::new(chunks)).unwrap();
+    assert_eq!(idents, vec!["foo", "bar", "baz", "quux"]);
+    assert!(matches!(idents[0], Cow::Borrowed(_)));
+    assert!(matches!(idents[2], Cow::Owned(_)));
+    assert!(matches!(idents[3], Cow::Borrowed(_)));
+}
+
+#[test]
+fn test_chunks_eat_slice_across_boundaries() {
+    let mut input = Pear::from(Chunks::new(["f", "n ", "ma", "in()"]));
+    assert_eq!(eat_slice(&mut input, "fn main").unwrap(), "fn main");
+    assert_eq!(eat(&mut input, '(').unwrap(), '(');
+    assert!(eat_slice(&mut input, ")x").is_err());
+    assert_eq!(eat_slice(&mut input, ")").unwrap(), ")");
+    assert!(eof(&mut input).is_ok());
+}
+
+#[test]
+fn test_chunks_multibyte() {
+    let mut input = Pear::from(Chunks::new(["a", "ß", "ç"]));
+    assert_eq!(take_while(&mut input, |_| true).unwrap(), "aßç");
+    assert!(input.seek_to(1));
+    assert_eq!(eat_any(&mut input).unwrap(), 'ß');
+    assert!(!input.seek_to(2));
+    assert_eq!(eat_any(&mut input).unwrap(), 'ç');
+    assert!(input.seek_to(5));
+    assert!(!input.seek_to(6));
+}
+
+#[test]
+fn test_chunks_rewind() {
+    let info = ParserInfo::new("test", true);
+    let mut input = Chunks::new(["ab", "cd"]);
+    let mark = input.mark(&info);
+    assert_eq!(input.take(|_| true), "abcd");
+    input.rewind_to(mark + 1);
+    assert_eq!(input.token(), Some('b'));
+    assert_eq!(input.slice(2).unwrap(), "bc");
+}
+
+#[test]
+fn test_chunks_error_context() {
+    let err = parse!(idents: Chunks::new(["foo,ba", "r,?"])).unwrap_err();
+    assert_eq!(err.info.context.end, (1, 9, 8));
+    assert_eq!(err.info.context.cursor, Some('?'));
+
+    let input = Chunks::new(["a\nb", "c\n", "d"]);
+    assert_eq!(input.position(3), (2, 2));
+    assert_eq!(input.position(5), (3, 1));
+}
+
+#[test]
+fn test_chunks_bytes() {
+    let chunks: [&[u8]; 3] = [&[1, 2], &[3], &[4, 5]];
+    let mut input = Pear::from(Chunks::new(chunks));
+    assert_eq!(eat_any(&mut input).unwrap(), 1);
+    assert_eq!(take_n_if(&mut input, 3, |_| true).unwrap(), &[2u8, 3, 4][..]);
+    assert_eq!(eat_any(&mut input).unwrap(), 5);
+}
@@ -0,0 +1,46 @@
+use pear::input::{Pear, Text, Span, ColumnUnit};
+use pear::{macros::*, parsers::*};
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn value<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, Span<'a>> {
+    take_while(|&c| c != '=')?;
+    eat('=')?;
+    parse_mark!();
+    take_while(|_| true)?;
+    parse_context!()
+}
+
+fn value_span(source: &str, columns: ColumnUnit) -> Span<'_> {
+    parse!(value: Text::from(source).with_columns(columns)).unwrap()
+}
+
+#[test]
+fn test_column_units() {
+    let source = "\tnaïve 😀=x";
+    let span = value_span(source, ColumnUnit::Byte);
+    assert_eq!((span.start, span.end), ((1, 14, 13), (1, 15, 14)));
+    assert_eq!(span.columns, ColumnUnit::Byte);
+
+    let span = value_span(source, ColumnUnit::Char);
+    assert_eq!((span.start, span.end), ((1, 10, 13), (1, 11, 14)));
+    assert_eq!(span.columns, ColumnUnit::Char);
+
+    let span = value_span(source, ColumnUnit::Utf16);
+    assert_eq!((span.start, span.end), ((1, 11, 13), (1, 12, 14)));
+
+    let span = value_span(source, ColumnUnit::Visual { tab_width: 4 });
+    assert_eq!((span.start, span.end), ((1, 13, 13), (1, 14, 14)));
+    assert_eq!(span.columns, ColumnUnit::Visual { tab_width: 4 });
+}
+
+#[test]
+fn test_column_units_multiline() {
+    let source = "a\n\t\tλ=yz";
+    let span = value_span(source, ColumnUnit::Byte);
+    assert_eq!((span.start, span.end), ((2, 6, 7), (2, 8, 9)));
+
+    let span = value_span(source, ColumnUnit::Visual { tab_width: 8 });
+    assert_eq!((span.start, span.end), ((2, 19, 7), (2, 21, 9)));
+}
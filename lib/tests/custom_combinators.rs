@@ -0,0 +1,112 @@
+use pear::input::{Text, Pear, Span, Expected};
+use pear::error;
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Result<'a, T> = pear::result::Result<T, Span<'a>, Error<'a>>;
+
+#[derive(Debug)]
+enum Error<'a> {
+    Expected(Expected<Text<'a>>),
+    Overflow(&'a str),
+    Duplicate(u8),
+}
+
+impl<'a> From<Expected<Text<'a>>> for Error<'a> {
+    fn from(other: Expected<Text<'a>>) -> Error<'a> {
+        Error::Expected(other)
+    }
+}
+
+#[parser]
+fn number<'a, D>(input: &mut Pear<Text<'a>, D>) -> Result<'a, u8> {
+    let digits = take_some_while(|c| c.is_ascii_digit())?;
+    match digits.parse::<u8>() {
+        Ok(n) => n,
+        Err(_) => parse_error!(Error::Overflow(digits))?,
+    }
+}
+
+#[parser]
+fn set<'a, D>(input: &mut Pear<Text<'a>, D>) -> Result<'a, Vec<u8>> {
+    let numbers: Vec<u8> = delimited_collect('{', number, ',', '}')?;
+    for (i, n) in numbers.iter().enumerate() {
+        if numbers[..i].contains(n) {
+            parse_error!(Error::Duplicate(*n))?;
+        }
+    }
+
+    numbers
+}
+
+#[parser]
+fn padded_number<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, u8> {
+    let n: u8 = surrounded(number, |&c| c == ' ')?;
+    n
+}
+
+#[parser]
+fn sets<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, Vec<Vec<u8>>> {
+    let sets: Vec<_> = series(set, ';')?;
+    sets
+}
+
+#[parser]
+fn lenient_sets<'a>(input: &mut Pear<Text<'a>, Error<'a>>) -> Result<'a, Vec<Vec<u8>>> {
+    let mut sets = vec![];
+    loop {
+        sets.push(recover(set, |&c| c == ';')?);
+        if !succeeds(input, |i| eat(i, ';')) {
+            break;
+        }
+    }
+
+    sets
+}
+
+#[test]
+fn test_custom_errors_pass_through_combinators() {
+    assert_eq!(parse!(sets: Text::from("{1,2};{};{3}")).unwrap(), vec![vec![1, 2], vec![], vec![3]]);
+
+    let err = parse!(sets: Text::from("{1,2};{300}")).unwrap_err();
+    assert!(matches!(err.error, Error::Overflow("300")));
+
+    let err = parse!(sets: Text::from("{1,2,1}")).unwrap_err();
+    assert!(matches!(err.error, Error::Duplicate(1)));
+
+    let err = parse!(sets: Text::from("{1,2")).unwrap_err();
+    assert!(matches!(err.error, Error::Expected(error::Expected::Token(..))));
+
+    let err = parse!(sets: Text::from("{1};{2}x")).unwrap_err();
+    assert!(matches!(err.error, Error::Expected(error::Expected::Eof(Some('x')))));
+
+    assert_eq!(parse!(padded_number: Text::from("  7 ")).unwrap(), 7);
+    let err = parse!(padded_number: Text::from(" 256")).unwrap_err();
+    assert!(matches!(err.error, Error::Overflow("256")));
+}
+
+#[test]
+fn test_custom_errors_with_optional_combinators() {
+    let mut input = Pear::from(Text::from("{1}{999}{2}"));
+    assert_eq!(ok(&mut input, set), Some(vec![1]));
+    assert!(!succeeds(&mut input, set));
+
+    let mut input = Pear::from(Text::from("{1}{2}{2,2}"));
+    let all: Vec<Vec<u8>> = try_collect(&mut input, set).unwrap();
+    assert_eq!(all, vec![vec![1], vec![2]]);
+
+    let mut input = Pear::from(Text::from("{1}{2}"));
+    assert_eq!(last_of_many(&mut input, set).unwrap(), vec![2]);
+
+    let mut input = Pear::from(Text::from("{1}{2}{1000}"));
+    let result: Result<'_, Vec<_>> = collect(&mut input, set);
+    assert!(matches!(result.unwrap_err().error, Error::Overflow("1000")));
+}
+
+#[test]
+fn test_custom_errors_recover() {
+    let (sets, diagnostics) = parse!(recover lenient_sets: Text::from("{1};{1,1};{256};{2}"));
+    assert_eq!(sets.unwrap(), vec![vec![1], vec![], vec![], vec![2]]);
+    assert_eq!(diagnostics.len(), 2);
+    assert!(matches!(diagnostics[0].error, Error::Duplicate(1)));
+    assert!(matches!(diagnostics[1].error, Error::Overflow("256")));
+}
@@ -6,6 +6,7 @@ use pear::{macros::*, parsers::*};
 type Result<'a, T> = pear::result::Result<T, Span<'a>, Error<'a>>;
 
 #[derive(Debug)]
+#[allow(dead_code)]
 enum Error<'a> {
     Expected(Expected<Text<'a>>),
     Other {
@@ -0,0 +1,167 @@
+use pear::input::{Pear, Text};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[derive(Debug, PartialEq)]
+enum Value<'a> {
+    Object(&'a str),
+    Raw(&'a str),
+}
+
+#[parser]
+fn object<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, &'a str> {
+    eat('{')?;
+    cut!();
+    let key = take_some_while(|c| c.is_ascii_alphabetic())?;
+    eat('}')?;
+    key
+}
+
+#[parser(commit)]
+fn open<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, char> {
+    eat('{')?
+}
+
+#[parser]
+fn committed_object<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, &'a str> {
+    open()?;
+    let key = take_some_while(|c| c.is_ascii_alphabetic())?;
+    eat('}')?;
+    key
+}
+
+#[parser(rewind)]
+fn uncut_object<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, &'a str> {
+    eat('{')?;
+    let key = take_some_while(|c| c.is_ascii_alphabetic())?;
+    eat('}')?;
+    key
+}
+
+#[parser]
+fn raw<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, &'a str> {
+    take_while(|_| true)?
+}
+
+#[parser]
+fn value<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, Value<'a>> {
+    switch! {
+        key@object() => Value::Object(key),
+        text@raw() => Value::Raw(text),
+        _ => parse_error!("expected a value")?
+    }
+}
+
+#[parser]
+fn committed_value<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, Value<'a>> {
+    switch! {
+        key@committed_object() => Value::Object(key),
+        text@raw() => Value::Raw(text),
+        _ => parse_error!("expected a value")?
+    }
+}
+
+#[parser]
+fn uncut_value<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, Value<'a>> {
+    switch! {
+        key@uncut_object() => Value::Object(key),
+        text@raw() => Value::Raw(text),
+        _ => parse_error!("expected a value")?
+    }
+}
+
+#[parser]
+fn nested<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, Value<'a>> {
+    switch! {
+        v@value() => v,
+        _ => Value::Raw("")
+    }
+}
+
+#[parser]
+fn objects<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, Vec<&'a str>> {
+    let objects: Vec<_> = try_collect(|i| object(i))?;
+    objects
+}
+
+#[parser(rewind)]
+fn uncut_objects<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, Vec<&'a str>> {
+    let objects: Vec<_> = try_collect(|i| uncut_object(i))?;
+    objects
+}
+
+#[test]
+fn test_switch_falls_back_before_cut() {
+    assert_eq!(parse!(value: Text::from("{a}")).unwrap(), Value::Object("a"));
+    assert_eq!(parse!(value: Text::from("[1]")).unwrap(), Value::Raw("[1]"));
+    assert_eq!(parse!(uncut_value: Text::from("{1}")).unwrap(), Value::Raw("{1}"));
+}
+
+#[test]
+fn test_switch_propagates_after_cut() {
+    let err = parse!(value: Text::from("{1}")).unwrap_err();
+    assert_eq!(err.info.parser.name, "take_some_while");
+    assert_eq!(err.info.context.start, (1, 2, 1));
+
+    let err = parse!(value: Text::from("{a1")).unwrap_err();
+    assert_eq!(err.error.to_string(), "expected token '}' but found '1'");
+
+    let err = parse!(value: Text::from("{a")).unwrap_err();
+    assert_eq!(err.error.to_string(), "unexpected EOF: expected token '}'");
+
+    let err = parse!(nested: Text::from("{1}")).unwrap_err();
+    assert_eq!(err.info.context.start, (1, 2, 1));
+}
+
+#[test]
+fn test_commit_attribute() {
+    assert_eq!(parse!(committed_value: Text::from("{a}")).unwrap(), Value::Object("a"));
+    assert_eq!(parse!(committed_value: Text::from("a}")).unwrap(), Value::Raw("a}"));
+    assert!(parse!(committed_value: Text::from("{1}")).is_err());
+}
+
+#[test]
+fn test_try_collect_propagates_after_cut() {
+    assert_eq!(parse!(objects: Text::from("{a}{b}")).unwrap(), ["a", "b"]);
+
+    let input = &mut Pear::from(Text::from("{a}{1}"));
+    assert!(objects(input).is_err());
+
+    let input = &mut Pear::from(Text::from("{a}{1}"));
+    assert_eq!(uncut_objects(input).unwrap(), ["a"]);
+    assert_eq!(raw(input).unwrap(), "{1}");
+}
+
+#[test]
+fn test_trailing_series_propagates_after_cut() {
+    let input = &mut Pear::from(Text::from("{a},{b},"));
+    let objects: Vec<_> = trailing_series(input, object, ',').unwrap();
+    assert_eq!(objects, ["a", "b"]);
+
+    let input = &mut Pear::from(Text::from("{a},{1}"));
+    let result: Result<'_, Vec<_>> = trailing_series(input, object, ',');
+    let err = result.unwrap_err();
+    assert_eq!(err.info.parser.name, "take_some_while");
+    assert_eq!(err.info.context.start, (1, 6, 5));
+
+    let input = &mut Pear::from(Text::from("{a},{1}"));
+    let objects: Vec<_> = trailing_series(input, uncut_object, ',').unwrap();
+    assert_eq!(objects, ["a"]);
+    assert_eq!(raw(input).unwrap(), "{1}");
+}
+
+#[test]
+fn test_ok_leaves_cut() {
+    let input = &mut Pear::from(Text::from("{1}"));
+    assert!(ok(input, object).is_none());
+    assert!(input.is_cut());
+
+    let input = &mut Pear::from(Text::from("{a}"));
+    assert!(succeeds(input, object));
+    assert!(!input.is_cut());
+
+    let input = &mut Pear::from(Text::from("[1]"));
+    assert!(!succeeds(input, object));
+    assert!(!input.is_cut());
+}
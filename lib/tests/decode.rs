@@ -0,0 +1,112 @@
+use pear::input::{Pear, Text, Bytes, Decoded, Percent, Base64, Utf16};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*};
+
+type Uri<'a> = Decoded<Text<'a>, Percent>;
+type Result<'a, T> = pear::input::Result<T, Uri<'a>>;
+
+#[parser]
+fn segment<'a>(input: &mut Pear<Uri<'a>>) -> Result<'a, String> {
+    let bytes = take_while(|&b| b != b'/')?;
+    match String::from_utf8(bytes) {
+        Ok(string) => string,
+        Err(_) => parse_error!("segment is not UTF-8")?,
+    }
+}
+
+#[parser]
+fn path<'a>(input: &mut Pear<Uri<'a>>) -> Result<'a, Vec<String>> {
+    let mut segments = vec![];
+    while eat(input, b'/').is_ok() {
+        segments.push(segment()?);
+    }
+
+    eof()?;
+    segments
+}
+
+#[test]
+fn test_percent_decoding() {
+    let input = Decoded::new(Text::from("/a%20b/caf%C3%A9/%E2%9C%93"), Percent::new());
+    let segments = parse!(path: input).unwrap();
+    assert_eq!(segments, vec!["a b", "café", "✓"]);
+
+    let input = Decoded::new(Text::from("/na%C3%AFve/✓"), Percent::new());
+    let segments = parse!(path: input).unwrap();
+    assert_eq!(segments, vec!["naïve", "✓"]);
+
+    let mut input = Pear::from(Decoded::new(Text::from("a+b%2B"), Percent::form()));
+    assert_eq!(take_while(&mut input, |_| true).unwrap(), b"a b+");
+}
+
+#[test]
+fn test_percent_error_points_at_source() {
+    let input = Decoded::new(Text::from("/ok/b%E2%28%A1"), Percent::new());
+    let err = parse!(path: input).unwrap_err();
+    assert_eq!(err.info.context.start.2, 4);
+    assert_eq!(err.info.context.end.2, 14);
+
+    let input = Decoded::new(Text::from("/ok/a%20b%zz"), Percent::new());
+    let err = parse!(path: input).unwrap_err();
+    assert!(matches!(err.error, Expected::Other(ref msg) if msg.contains("invalid percent-encoding")));
+    assert_eq!(err.info.context.end.2, 9);
+    assert_eq!(err.info.context.cursor, Some('%'));
+}
+
+#[test]
+fn test_rewind_within_group() {
+    // `é` encodes to two bytes in a single group.
+    let mut input = Pear::from(Decoded::new(Text::from("é%41"), Percent::new()));
+    assert_eq!(eat(&mut input, 0xC3).unwrap(), 0xC3);
+    assert!(peek_slice(&mut input, &[0xA9, b'A'][..]).is_ok());
+    assert!(eat_slice(&mut input, &[0xA9, b'B'][..]).is_err());
+    assert_eq!(eat_any(&mut input).unwrap(), 0xA9);
+    assert_eq!(eat_any(&mut input).unwrap(), b'A');
+    assert!(eof(&mut input).is_ok());
+}
+
+#[test]
+fn test_base64() {
+    let decode = |s: &str, base64: Base64| {
+        let mut input = Pear::from(Decoded::new(Text::from(s), base64));
+        let bytes = take_while(&mut input, |_| true).map_err(|e| e.to_string())?;
+        eof(&mut input).map(|_| bytes).map_err(|e| e.to_string())
+    };
+
+    assert_eq!(decode("aGVsbG8=", Base64::standard()).unwrap(), b"hello");
+    assert_eq!(decode("aGVsbG8", Base64::standard()).unwrap(), b"hello");
+    assert_eq!(decode("aGk=", Base64::standard()).unwrap(), b"hi");
+    assert_eq!(decode("+/8=", Base64::standard()).unwrap(), [0xfb, 0xff]);
+    assert_eq!(decode("-_8", Base64::url_safe()).unwrap(), [0xfb, 0xff]);
+    assert!(decode("aGk==", Base64::standard()).unwrap_err().contains("padding"));
+    assert!(decode("aGVsb", Base64::standard()).unwrap_err().contains("truncated"));
+    assert!(decode("aGk!", Base64::standard()).unwrap_err().contains("character"));
+}
+
+#[parser]
+fn word<'a, D>(input: &mut Pear<Decoded<Bytes<'a>, D>>) -> pear::input::Result<String, Decoded<Bytes<'a>, D>>
+    where D: pear::input::Decoder<Bytes<'a>, Token = char>
+{
+    let chars = take_some_while(|c: &char| c.is_alphabetic())?;
+    chars.into_iter().collect::<String>()
+}
+
+#[test]
+fn test_utf16() {
+    let le: Vec<u8> = "héllo 𝄞".encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
+    let mut input = Pear::from(Decoded::new(Bytes::from(&le[..]), Utf16::le()));
+    assert_eq!(word(&mut input).unwrap(), "héllo");
+    assert_eq!(eat(&mut input, ' ').unwrap(), ' ');
+    assert_eq!(eat(&mut input, '𝄞').unwrap(), '𝄞');
+    assert!(eof(&mut input).is_ok());
+
+    let be: Vec<u8> = "ok".encode_utf16().flat_map(|u| u.to_be_bytes()).collect();
+    let mut input = Pear::from(Decoded::new(Bytes::from(&be[..]), Utf16::be()));
+    assert_eq!(word(&mut input).unwrap(), "ok");
+
+    let bad = [b'a', 0, 0x00, 0xDC, b'b', 0];
+    let mut input = Pear::from(Decoded::new(Bytes::from(&bad[..]), Utf16::le()));
+    let err = word(&mut input).unwrap_err();
+    assert!(err.to_string().contains("unpaired UTF-16 surrogate"));
+    assert_eq!(err.info.context.end.2, 2);
+}
@@ -0,0 +1,135 @@
+use pear::input::{Pear, Text};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*};
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn string<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, &'a str> {
+    eat('"')?;
+    let inner = take_while(|&c| c != '"')?;
+    eat('"')?;
+    inner
+}
+
+#[parser]
+fn after_value<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, ()> {
+    switch! {
+        eat('}') => (),
+        eat(',') => (),
+        _ => { string()?; }
+    }
+}
+
+#[parser(label = "string")]
+fn labeled_string<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, &'a str> {
+    string()?
+}
+
+#[parser]
+fn after_labeled_value<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, ()> {
+    switch! {
+        eat('}') => (),
+        eat(',') => (),
+        _ => { labeled_string()?; }
+    }
+}
+
+#[parser]
+fn labeled_value<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, ()> {
+    switch! {
+        labeled_string() => (),
+        eat('}') => (),
+        _ => { eat(',')?; }
+    }
+}
+
+#[parser]
+fn sign<'a>(input: &mut Pear<&'a str>) -> pear::input::Result<char, &'a str> {
+    switch! {
+        eat('+') => '+',
+        _ => eat('-')?
+    }
+}
+
+#[parser(rewind)]
+fn call<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, &'a str> {
+    let name = take_some_while(|c| c.is_ascii_alphabetic())?;
+    eat('(')?;
+    take_while(|c| c.is_ascii_digit())?;
+    eat(')')?;
+    name
+}
+
+#[parser]
+fn expr<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, &'a str> {
+    switch! {
+        call() => "call",
+        take_some_while(|c| c.is_ascii_alphabetic()) => "ident",
+        _ => parse_error!("expected an expression")?
+    }
+}
+
+fn tracking(text: &str) -> Pear<Text<'_>> {
+    let mut input = Pear::from(Text::from(text));
+    input.options.track_furthest = true;
+    input
+}
+
+#[test]
+fn test_merges_alternatives_at_same_position() {
+    let err = after_value(&mut tracking("x")).unwrap_err();
+    assert_eq!(err.error.to_string(), "expected one of: '}', ',', '\"' but found 'x'");
+    assert_eq!(err.info.context.start, (1, 1, 0));
+
+    let err = after_value(&mut tracking("")).unwrap_err();
+    assert_eq!(err.error.to_string(), "unexpected EOF: expected one of: '}', ',', '\"'");
+
+    let err = parse!(after_value: Text::from("x")).unwrap_err();
+    assert_eq!(err.error.to_string(), "expected token '\"' but found 'x'");
+}
+
+#[test]
+fn test_merges_labels_at_same_position() {
+    let err = after_labeled_value(&mut tracking("x")).unwrap_err();
+    assert_eq!(err.error.to_string(), "expected one of: '}', ',', string but found 'x'");
+
+    let err = labeled_value(&mut tracking("x")).unwrap_err();
+    assert_eq!(err.error.to_string(), "expected one of: string, '}', ',' but found 'x'");
+
+    let err = parse!(after_labeled_value: Text::from("x")).unwrap_err();
+    assert_eq!(err.error.to_string(), "expected string but found 'x'");
+}
+
+#[test]
+fn test_reports_furthest_failure() {
+    let mut input = tracking("f(12;");
+    assert_eq!(expr(&mut input).unwrap(), "ident");
+    assert_eq!(input.furthest().unwrap().progress, 4);
+
+    let err = eof(&mut input).unwrap_err();
+    assert_eq!(err.error.to_string(), "expected ')' but found ';'");
+    assert_eq!(err.info.context.start, (1, 5, 4));
+    assert!(input.furthest().is_none());
+
+    let mut input = tracking("f(12;");
+    let err = parse!(expr: &mut input).unwrap_err();
+    assert!(matches!(err.error, Expected::OneOf(ref labels, Some(';')) if labels.len() == 1));
+}
+
+#[test]
+fn test_single_expectations_are_unchanged() {
+    let mut input = tracking("ab");
+    eat(&mut input, 'a').unwrap();
+    let err = eat(&mut input, 'c').unwrap_err();
+    assert_eq!(err.error.to_string(), "expected token 'c' but found 'b'");
+
+}
+
+#[test]
+fn test_str_inputs() {
+    let mut input: Pear<&str> = Pear::new("x");
+    input.options.track_furthest = true;
+    let err = sign(&mut input).unwrap_err();
+    assert_eq!(err.error.to_string(), "expected one of: '+', '-' but found 'x'");
+}
@@ -0,0 +1,119 @@
+use pear::input::{Pear, IterInput, Input, ParserInfo, Rewind};
+use pear::{macros::*, parsers::*};
+
+type Result<T, I> = pear::input::Result<T, IterInput<I>>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(&'static str),
+    Int(i64),
+    Plus,
+    Minus,
+}
+
+impl pear::input::Show for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[parser]
+fn operand<I>(input: &mut Pear<IterInput<I>>) -> Result<i64, I>
+    where I: Iterator<Item = Token>
+{
+    match eat_any()? {
+        Token::Int(n) => n,
+        Token::Ident(_) => 0,
+        _ => parse_error!("expected an operand")?,
+    }
+}
+
+#[parser]
+fn sum<I>(input: &mut Pear<IterInput<I>>) -> Result<i64, I>
+    where I: Iterator<Item = Token>
+{
+    let mut total = operand()?;
+    loop {
+        switch! {
+            eat(Token::Plus) => total += operand()?,
+            eat(Token::Minus) => total -= operand()?,
+            _ => break
+        }
+    }
+
+    eof()?;
+    total
+}
+
+#[parser(rewind)]
+fn keyword<I: Iterator<Item = char>>(input: &mut Pear<IterInput<I>>, kw: &str) -> Result<(), I> {
+    for c in kw.chars() {
+        eat(c)?;
+    }
+}
+
+#[parser]
+fn word<I: Iterator<Item = char>>(input: &mut Pear<IterInput<I>>) -> Result<usize, I> {
+    switch! {
+        keyword("let") => 0,
+        keyword("lex") => 1,
+        _ => take_some_while(|c| c.is_alphabetic())?.len()
+    }
+}
+
+#[test]
+fn test_iter_lexer_tokens() {
+    use Token::*;
+
+    let tokens = vec![Int(1), Plus, Int(2), Minus, Ident("x"), Plus, Int(10)];
+    assert_eq!(parse!(sum: IterInput::new(tokens.into_iter())).unwrap(), 13);
+
+    let tokens = vec![Int(1), Plus, Minus];
+    let err = parse!(sum: IterInput::new(tokens.into_iter())).unwrap_err();
+    assert_eq!(err.info.context.start, 2);
+    assert_eq!(err.info.context.values, vec![Minus]);
+}
+
+#[test]
+fn test_iter_rewinds_across_lookahead() {
+    let mut input: Pear<IterInput<_>> = Pear::new("lex lexicon lettuce".chars());
+    assert_eq!(word(&mut input).unwrap(), 1);
+    assert_eq!(eat(&mut input, ' ').unwrap(), ' ');
+    assert_eq!(word(&mut input).unwrap(), 1);
+    assert_eq!(word(&mut input).unwrap(), 4);
+    assert_eq!(eat(&mut input, ' ').unwrap(), ' ');
+    assert_eq!(word(&mut input).unwrap(), 0);
+    assert_eq!(word(&mut input).unwrap(), 4);
+    assert!(eof(&mut input).is_ok());
+}
+
+#[test]
+fn test_iter_memory_is_bounded() {
+    let mut input: Pear<IterInput<_>> = Pear::new("abc def ".chars().cycle());
+    for _ in 0..10_000 {
+        assert_eq!(word(&mut input).unwrap(), 3);
+        assert_eq!(eat(&mut input, ' ').unwrap(), ' ');
+        assert!(input.buffered() <= 8, "buffered {}", input.buffered());
+    }
+
+    assert_eq!(input.offset(), 40_000);
+}
+
+#[test]
+fn test_iter_live_marker_retains_items() {
+    let info = ParserInfo::new("test", true);
+    let mut input = IterInput::new(0..1000u32);
+    let start = input.mark(&info);
+    assert_eq!(input.skip(|&n| n < 500), 500);
+    assert!(input.has(10));
+    assert_eq!(input.window_start(), 0);
+
+    input.rewind_to(start);
+    assert_eq!(input.token(), Some(0));
+    input.unmark(start);
+
+    assert_eq!(input.skip(|&n| n < 900), 900);
+    assert!(input.has(10));
+    assert_eq!(input.window_start(), 900);
+    assert_eq!(input.context(600).values, &[][..]);
+}
@@ -0,0 +1,92 @@
+use pear::input::{Pear, Text};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser(label = "quoted string")]
+fn string<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, &'a str> {
+    eat('"')?;
+    let inner = take_while(|&c| c != '"')?;
+    eat('"')?;
+    inner
+}
+
+#[parser(label = "key-value pair")]
+fn key_value_pair<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, (&'a str, &'a str)> {
+    let key = take_some_while(|c| c.is_ascii_alphabetic())?;
+    eat('=')?;
+    (key, string()?)
+}
+
+#[parser]
+fn pairs<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, Vec<(&'a str, &'a str)>> {
+    let pairs: Vec<_> = series(|i| key_value_pair(i), ',')?;
+    pairs
+}
+
+#[parser(rewind, label = "number")]
+fn number<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, &'a str> {
+    take_some_while(|c| c.is_ascii_digit())?
+}
+
+#[parser(label = "identifier")]
+fn identifier<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, &'a str> {
+    switch! {
+        peek_if(|c| c.is_ascii_digit()) => parse_error!("identifier starts with a digit")?,
+        _ => take_some_while(|c| c.is_ascii_alphanumeric())?
+    }
+}
+
+#[test]
+fn test_label_replaces_error_without_consumption() {
+    let err = string(&mut Text::from("x").into()).unwrap_err();
+    assert_eq!(err.error.to_string(), "expected quoted string but found 'x'");
+    assert_eq!(err.info.parser.name, "string");
+    assert_eq!(err.info.parser.label, Some("quoted string"));
+
+    let err = string(&mut Text::from("").into()).unwrap_err();
+    assert_eq!(err.error.to_string(), "unexpected EOF: expected quoted string");
+}
+
+#[test]
+fn test_label_keeps_error_after_consumption() {
+    let err = string(&mut Text::from("\"abc").into()).unwrap_err();
+    assert_eq!(err.error.to_string(), "unexpected EOF: expected token '\"'");
+    assert_eq!(err.info.parser.name, "eat");
+}
+
+#[test]
+fn test_label_is_innermost_unconsumed_parser() {
+    let err = pairs(&mut Text::from("a=x").into()).unwrap_err();
+    assert_eq!(err.error.to_string(), "expected quoted string but found 'x'");
+    assert!(err.to_string().contains("+ key-value pair"));
+
+    let err = pairs(&mut Text::from("=").into()).unwrap_err();
+    assert_eq!(err.error.to_string(), "expected key-value pair but found '='");
+}
+
+#[test]
+fn test_label_with_other_arguments() {
+    let input = &mut Pear::from(Text::from("x"));
+    let err = number(input).unwrap_err();
+    assert_eq!(err.error.to_string(), "expected number but found 'x'");
+    assert_eq!(number(&mut Text::from("12").into()).unwrap(), "12");
+}
+
+#[test]
+fn test_label_keeps_other_errors() {
+    let err = identifier(&mut Text::from("1x").into()).unwrap_err();
+    assert_eq!(err.error.to_string(), "identifier starts with a digit");
+    assert_eq!(err.info.parser.name, "identifier");
+
+    let err = identifier(&mut Text::from("-").into()).unwrap_err();
+    assert_eq!(err.error.to_string(), "expected identifier but found '-'");
+}
+
+#[test]
+fn test_label_keeps_stack() {
+    let err = string(&mut Text::from("x").into()).unwrap_err();
+    assert_eq!(err.stack.len(), 2);
+    assert_eq!(err.stack[0].parser.name, "eat");
+    assert_eq!(err.stack[1].parser.name, "string");
+}
@@ -0,0 +1,122 @@
+use pear::input::{Pear, Text};
+use pear::{macros::*, parsers::*};
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn int<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, i64> {
+    take_some_while(|c| c.is_ascii_digit())?.parse().or_else(|e| parse_error!("{}", e)?)
+}
+
+#[parser(rewind)]
+fn binary<'a, P, Q>(input: &mut Pear<Text<'a>>, left: P, op: char, right: Q) -> Result<'a, (i64, i64)>
+    where P: FnOnce(&mut Pear<Text<'a>>) -> Result<'a, i64>,
+          Q: FnOnce(&mut Pear<Text<'a>>) -> Result<'a, i64>
+{
+    let left = left()?;
+    eat(op)?;
+    (left, right()?)
+}
+
+// expr := expr '+' term | expr '-' term | term
+#[parser(memo)]
+fn expr<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, i64> {
+    switch! {
+        v@binary(expr, '+', term) => v.0 + v.1,
+        v@binary(expr, '-', term) => v.0 - v.1,
+        _ => term()?
+    }
+}
+
+// term := term '*' factor | term '/' factor | factor
+#[parser(memo)]
+fn term<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, i64> {
+    switch! {
+        v@binary(term, '*', factor) => v.0 * v.1,
+        v@binary(term, '/', factor) => v.0 / v.1,
+        _ => factor()?
+    }
+}
+
+// factor := '(' expr ')' | int
+#[parser]
+fn factor<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, i64> {
+    switch! {
+        eat('(') => (expr()?, eat(')')?).0,
+        _ => int()?
+    }
+}
+
+// list := element ',' 'a' | 'a'
+// element := list
+#[parser(memo)]
+fn list<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, String> {
+    switch! {
+        l@items() => format!("({},a)", l.0),
+        eat('a') => "a".to_string(),
+        _ => parse_error!("expected a list")?
+    }
+}
+
+#[parser(rewind)]
+fn items<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, (String, char)> {
+    let list = element()?;
+    eat(',')?;
+    (list, eat('a')?)
+}
+
+#[parser(memo)]
+fn element<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, String> {
+    list()?
+}
+
+#[parser(memo)]
+fn forever<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, char> {
+    forever()?;
+    eat('x')?
+}
+
+#[test]
+fn test_direct_left_recursion() {
+    assert_eq!(parse!(expr: Text::from("7")).unwrap(), 7);
+    assert_eq!(parse!(expr: Text::from("8-3-2")).unwrap(), 3);
+    assert_eq!(parse!(expr: Text::from("100/10/5")).unwrap(), 2);
+    assert_eq!(parse!(expr: Text::from("2*3+4*5-6/2")).unwrap(), 23);
+    assert_eq!(parse!(expr: Text::from("(1+2)*3-(4-1-1)")).unwrap(), 7);
+}
+
+#[test]
+fn test_left_recursion_stops_growing() {
+    let input = &mut Pear::from(Text::from("1-2-3)"));
+    assert_eq!(expr(input).unwrap(), -4);
+    assert!(eat(input, ')').is_ok());
+
+    assert!(parse!(expr: Text::from("1-2-")).is_err());
+    assert!(parse!(expr: Text::from("-1")).is_err());
+}
+
+#[test]
+fn test_indirect_left_recursion() {
+    assert_eq!(parse!(list: Text::from("a")).unwrap(), "a");
+    assert_eq!(parse!(list: Text::from("a,a,a")).unwrap(), "((a,a),a)");
+
+    let input = &mut Pear::from(Text::from("a,a,a"));
+    assert_eq!(element(input).unwrap(), "((a,a),a)");
+    assert!(eof(input).is_ok());
+}
+
+#[test]
+fn test_left_recursion_without_base_case() {
+    let err = parse!(forever: Text::from("xx")).unwrap_err();
+    assert_eq!(err.error.to_string(), "no base case for left-recursive forever");
+}
+
+#[test]
+fn test_left_recursion_is_memoized() {
+    let input = &mut Pear::from(Text::from("1+2*3"));
+    assert_eq!(expr(input).unwrap(), 7);
+
+    let stats = input.memo_stats();
+    let (_, expr) = stats.iter().find(|(k, _)| k.ends_with("::expr")).unwrap();
+    assert_eq!(expr.misses, 1);
+}
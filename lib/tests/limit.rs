@@ -0,0 +1,160 @@
+use pear::input::{Pear, Bytes, ByteSpan, Limited, Text};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Pear<Bytes<'a>>;
+type Result<'a, T> = pear::input::Result<T, Bytes<'a>>;
+
+type Window<'a, 'b> = Pear<Limited<'b, Bytes<'a>>>;
+type WindowResult<'a, 'b, T> = pear::input::Result<T, Limited<'b, Bytes<'a>>>;
+type TextResult<'a, 'b, T> = pear::input::Result<T, Limited<'b, Text<'a>>>;
+
+#[derive(Debug, PartialEq)]
+enum Value<'a> {
+    Str(&'a [u8]),
+    List(Vec<Value<'a>>),
+}
+
+#[parser]
+fn str<'a, 'b>(input: &mut Window<'a, 'b>) -> WindowResult<'a, 'b, Value<'a>> {
+    Value::Str(take_while(|c| c.is_ascii_alphanumeric())?)
+}
+
+#[parser]
+fn pair<'a, 'b>(input: &mut Window<'a, 'b>) -> WindowResult<'a, 'b, Value<'a>> {
+    let a = take_n(1)?;
+    let b = take_n(1)?;
+    Value::List(vec![Value::Str(a), Value::Str(b)])
+}
+
+/// A record: a one-byte tag, a one-byte length, and a value of that length.
+#[parser]
+fn record<'a>(input: &mut Input<'a>) -> Result<'a, Value<'a>> {
+    let tag = eat_any()?;
+    let len = eat_any()? as usize;
+    switch! {
+        _ if tag == b's' => limit(len, str)?,
+        _ if tag == b'p' => limit_skip(len, pair)?,
+        _ => parse_error!("unknown tag")?
+    }
+}
+
+#[parser]
+fn records<'a>(input: &mut Input<'a>) -> Result<'a, Vec<Value<'a>>> {
+    let records: Vec<_> = collect(record)?;
+    records
+}
+
+#[test]
+fn test_limit_records() {
+    let values = parse!(records: Bytes::from(b"s\x03abcp\x04xyzws\x00")).unwrap();
+    assert_eq!(values, vec![
+        Value::Str(b"abc"),
+        Value::List(vec![Value::Str(b"x"), Value::Str(b"y")]),
+        Value::Str(b""),
+    ]);
+}
+
+#[test]
+fn test_limit_window_end() {
+    // The string parser would happily continue past the end of the window.
+    let values = parse!(records: Bytes::from(b"s\x02abs\x01c")).unwrap();
+    assert_eq!(values, vec![Value::Str(b"ab"), Value::Str(b"c")]);
+
+    // But the window is opaque to lookahead: `pair` can't see past it.
+    let err = parse!(records: Bytes::from(b"p\x01ab")).unwrap_err();
+    assert!(matches!(err.error, Expected::Token(None, None)), "{:?}", err.error);
+}
+
+#[test]
+fn test_limit_unconsumed() {
+    let err = parse!(records: Bytes::from(b"s\x00s\x03a-b")).unwrap_err();
+    assert!(matches!(err.error, Expected::Eof(Some(b'-'))), "{:?}", err.error);
+
+    // Positions are absolute, not relative to the window.
+    let ByteSpan { start, end, .. } = err.info.context;
+    assert_eq!((start, end), ((1, 5, 4), (1, 6, 5)));
+}
+
+#[test]
+fn test_limit_short_input() {
+    let err = parse!(records: Bytes::from(b"s\x05ab")).unwrap_err();
+    assert!(matches!(err.error, Expected::Slice(None, None)), "{:?}", err.error);
+
+    let err = parse!(records: Bytes::from(b"p\x05ab")).unwrap_err();
+    assert!(matches!(err.error, Expected::Slice(None, None)), "{:?}", err.error);
+}
+
+#[parser]
+fn rest<'a, 'b>(input: &mut Pear<Limited<'b, Text<'a>>>) -> TextResult<'a, 'b, &'a str> {
+    take_while(|_| true)?
+}
+
+#[test]
+fn test_limit_text_char_boundary() {
+    let mut input = Pear::from(Text::from("aé!"));
+    let window = limit(&mut input, 3, rest).unwrap();
+    assert_eq!(window, "aé");
+    assert_eq!(eat_any(&mut input).unwrap(), '!');
+
+    // A window that ends inside of a character can't be fully consumed.
+    let mut input = Pear::from(Text::from("aé!"));
+    assert!(limit_skip(&mut input, 2, rest).is_err());
+}
+
+#[parser]
+fn bracketed<'a, 'b>(input: &mut Window<'a, 'b>) -> WindowResult<'a, 'b, Value<'a>> {
+    switch! {
+        eat(b'<') => (),
+        _ => ()
+    }
+
+    Value::Str(take_while(|&c| c != b'>')?)
+}
+
+#[parser]
+fn lenient<'a, 'b>(input: &mut Window<'a, 'b>) -> WindowResult<'a, 'b, Value<'a>> {
+    recover(|i| eat(i, b'<'), |&c| c == b'!')?;
+    Value::Str(take_while(|_| true)?)
+}
+
+#[test]
+fn test_limit_shares_state() {
+    let mut input = Pear::from(Bytes::from(b"x-bc!"));
+    input.options.track_furthest = true;
+    assert_eq!(limit(&mut input, 2, bracketed).unwrap(), Value::Str(b"x-"));
+    let furthest = input.furthest().unwrap();
+    assert_eq!(furthest.progress, 0);
+    assert_eq!(furthest.expected, ["'<'"]);
+
+    assert_eq!(limit(&mut input, 2, lenient).unwrap(), Value::Str(b""));
+    assert_eq!(input.diagnostics().len(), 1);
+    assert_eq!(input.diagnostics()[0].info.context.start.2, 2);
+}
+
+#[parser]
+fn object<'a, 'b>(input: &mut Window<'a, 'b>) -> WindowResult<'a, 'b, Value<'a>> {
+    eat(b'{')?;
+    cut!();
+    let key = take_some_while(|c| c.is_ascii_alphabetic())?;
+    eat(b'}')?;
+    Value::Str(key)
+}
+
+#[parser]
+fn value<'a>(input: &mut Input<'a>) -> Result<'a, Value<'a>> {
+    switch! {
+        v@limit(3, object) => v,
+        _ => Value::Str(take_while(|_| true)?)
+    }
+}
+
+#[test]
+fn test_limit_keeps_cut() {
+    assert_eq!(parse!(value: Bytes::from(b"{a}")).unwrap(), Value::Str(b"a"));
+    assert_eq!(parse!(value: Bytes::from(b"[1]")).unwrap(), Value::Str(b"[1]"));
+
+    let err = parse!(value: Bytes::from(b"{1}")).unwrap_err();
+    assert_eq!(err.info.parser.name, "take_some_while");
+    assert_eq!(err.info.context.start, (1, 2, 1));
+}
@@ -88,36 +88,16 @@ fn resetting_context<'a>(input: &mut Input<'a>) -> Result<'a, TwoSpans<'a>> {
 fn test_context() {
     let (first, second) = parse!(context: Input::new("...\n..")).unwrap();
 
-    assert_eq!(first, Span {
-        start: (1, 1, 0),
-        end: (1, 4, 3),
-        snippet: Some("..."),
-        cursor: Some('\n'),
-    });
-
-    assert_eq!(second, Span {
-        start: (1, 1, 0),
-        end: (2, 3, 6),
-        snippet: Some("...\n.."),
-        cursor: None,
-    });
+    assert_eq!(first, Span::new((1, 1, 0), (1, 4, 3), Some('\n'), Some("...")));
+
+    assert_eq!(second, Span::new((1, 1, 0), (2, 3, 6), None, Some("...\n..")));
 }
 
 #[test]
 fn test_resetting_context() {
     let (first, second) = parse!(resetting_context: Input::new("...\n..")).unwrap();
 
-    assert_eq!(first, Span {
-        start: (1, 1, 0),
-        end: (1, 4, 3),
-        snippet: Some("..."),
-        cursor: Some('\n'),
-    });
-
-    assert_eq!(second, Span {
-        start: (2, 1, 4),
-        end: (2, 3, 6),
-        snippet: Some(".."),
-        cursor: None,
-    });
+    assert_eq!(first, Span::new((1, 1, 0), (1, 4, 3), Some('\n'), Some("...")));
+
+    assert_eq!(second, Span::new((2, 1, 4), (2, 3, 6), None, Some("..")));
 }
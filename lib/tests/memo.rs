@@ -0,0 +1,152 @@
+use std::cell::Cell;
+
+use pear::input::{Pear, Text, Span, Expected, MemoStats};
+use pear::{macros::*, parsers::*};
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+thread_local!(static RUNS: Cell<usize> = const { Cell::new(0) });
+
+fn runs() -> usize {
+    RUNS.with(|runs| runs.replace(0))
+}
+
+#[parser(memo)]
+fn word<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, String> {
+    RUNS.with(|runs| runs.set(runs.get() + 1));
+    take_some_while(|c| c.is_ascii_alphabetic())?.to_string()
+}
+
+#[parser(rewind)]
+fn exclamation<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, String> {
+    let word = word()?;
+    eat('!')?;
+    word
+}
+
+#[parser(rewind)]
+fn question<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, String> {
+    let word = word()?;
+    eat('?')?;
+    word
+}
+
+#[parser]
+fn sentence<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, (String, char)> {
+    switch! {
+        w@exclamation() => (w, '!'),
+        w@question() => (w, '?'),
+        _ => (word()?, '.')
+    }
+}
+
+fn stats(input: &Pear<Text<'_>>) -> MemoStats {
+    input.memo_stats().iter()
+        .find(|(key, _)| key.ends_with("::word"))
+        .map(|(_, stats)| *stats)
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_memo_replays_output_and_position() {
+    let input = &mut Pear::from(Text::from("hello?"));
+    assert_eq!(sentence(input).unwrap(), ("hello".to_string(), '?'));
+    assert_eq!(runs(), 1);
+    assert_eq!(stats(input), MemoStats { hits: 1, misses: 1 });
+    assert_eq!(stats(input).hit_rate(), 0.5);
+
+    let input = &mut Pear::from(Text::from("hello"));
+    assert_eq!(sentence(input).unwrap(), ("hello".to_string(), '.'));
+    assert_eq!(runs(), 1);
+    assert_eq!(stats(input), MemoStats { hits: 2, misses: 1 });
+    assert!(eof(input).is_ok());
+}
+
+#[test]
+fn test_memo_replays_errors() {
+    let input = &mut Pear::from(Text::from("1"));
+    let err = sentence(input).unwrap_err();
+    assert_eq!(err.info.parser.name, "take_some_while");
+    assert_eq!(err.info.context.start, (1, 1, 0));
+    assert_eq!(runs(), 2);
+    assert_eq!(stats(input), MemoStats { hits: 1, misses: 2 });
+
+    assert!(word(input).is_err());
+    assert_eq!(runs(), 0);
+    assert_eq!(stats(input).hits, 2);
+}
+
+#[parser(memo)]
+fn bang<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, char> {
+    RUNS.with(|runs| runs.set(runs.get() + 1));
+    eat('!')?
+}
+
+#[parser]
+fn maybe_bang<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, char> {
+    switch! {
+        c@bang() => c,
+        _ => bang()?
+    }
+}
+
+#[test]
+fn test_memo_skips_elided_errors() {
+    let input = &mut Pear::from(Text::from("x"));
+    let err = maybe_bang(input).unwrap_err();
+    assert_eq!(err.error.to_string(), "expected token '!' but found 'x'");
+    assert_eq!(runs(), 2);
+
+    assert_eq!(maybe_bang(input).unwrap_err().to_string(), err.to_string());
+    assert_eq!(runs(), 0);
+}
+
+#[test]
+fn test_clear_memo() {
+    let input = &mut Pear::from(Text::from("hi!"));
+    word(input).unwrap();
+    input.clear_memo();
+    assert_eq!(runs(), 1);
+
+    assert_eq!(sentence(&mut Pear::from(Text::from("hi!"))).unwrap().1, '!');
+    assert_eq!(runs(), 1);
+    assert_eq!(stats(input), MemoStats { hits: 0, misses: 1 });
+}
+
+#[derive(Debug, Clone)]
+enum Error<'a> {
+    Expected(Expected<Text<'a>>),
+    Reserved(&'a str),
+}
+
+impl<'a> From<Expected<Text<'a>>> for Error<'a> {
+    fn from(expected: Expected<Text<'a>>) -> Error<'a> {
+        Error::Expected(expected)
+    }
+}
+
+impl_show_with!(Debug, Error<'_>);
+
+type CustomResult<'a, T> = pear::result::Result<T, Span<'a>, Error<'a>>;
+
+#[parser(memo, rewind)]
+fn name<'a>(input: &mut Pear<Text<'a>, Error<'a>>) -> CustomResult<'a, String> {
+    RUNS.with(|runs| runs.set(runs.get() + 1));
+    let name = take_some_while(|c| c.is_ascii_alphabetic())?;
+    if name == "let" {
+        parse_error!(Error::Reserved(name))?;
+    }
+
+    name.to_string()
+}
+
+#[test]
+fn test_memo_replays_custom_errors() {
+    let input = &mut Pear::with_error_type(Text::from("let"));
+    assert!(matches!(name(input).unwrap_err().error, Error::Reserved("let")));
+    assert!(matches!(name(input).unwrap_err().error, Error::Reserved("let")));
+    assert_eq!(runs(), 1);
+
+    let input = &mut Pear::with_error_type(Text::from("1"));
+    assert!(matches!(name(input).unwrap_err().error, Error::Expected(pear::error::Expected::Token(None, _))));
+}
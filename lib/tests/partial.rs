@@ -0,0 +1,80 @@
+use pear::input::{Pear, Cursor, Partial};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*};
+
+type Input<'a> = Pear<Partial<Cursor<&'a [u8]>>>;
+type Result<'a, T> = pear::input::Result<T, Partial<Cursor<&'a [u8]>>>;
+
+/// A frame is `LEN:PAYLOAD;` where `LEN` is the length of `PAYLOAD`.
+#[parser]
+fn frame<'a>(input: &mut Input<'a>) -> Result<'a, &'a [u8]> {
+    let digits = take_some_while(|c| c.is_ascii_digit())?;
+    let len = std::str::from_utf8(&digits).unwrap().parse().unwrap();
+    eat(b':')?;
+    let payload = take_n_if(len, |_| true)?;
+    eat(b';')?;
+    payload.values
+}
+
+fn partial(bytes: &[u8]) -> Input<'_> {
+    Input::from(Partial::new(Cursor::from(bytes)))
+}
+
+#[test]
+fn test_partial_complete_frame() {
+    assert_eq!(frame(&mut partial(b"5:hello;")).unwrap(), b"hello");
+    assert_eq!(frame(&mut partial(b"5:hello;5:")).unwrap(), b"hello");
+}
+
+#[test]
+fn test_partial_incomplete_frame() {
+    let needed = |bytes| match frame(&mut partial(bytes)).unwrap_err().error {
+        Expected::Incomplete(n) => n,
+        other => panic!("expected incomplete, found {:?}", other),
+    };
+
+    assert_eq!(needed(b""), 1);
+    assert_eq!(needed(b"5"), 1);
+    assert_eq!(needed(b"5:"), 5);
+    assert_eq!(needed(b"5:hel"), 2);
+    assert_eq!(needed(b"5:hello"), 1);
+}
+
+#[test]
+fn test_partial_malformed_frame() {
+    let error = frame(&mut partial(b"5:hello!")).unwrap_err();
+    assert!(matches!(error.error, Expected::Token(Some(_), Some(b'!'))));
+}
+
+#[test]
+fn test_partial_retry() {
+    let stream: &[&[u8]] = &[b"1", b"1:hello", b" world;3", b":", b"foo;"];
+    let (mut buffer, mut frames) = (vec![], vec![]);
+    for chunk in stream {
+        buffer.extend_from_slice(chunk);
+        loop {
+            let consumed = {
+                let mut input = partial(&buffer);
+                match frame(&mut input) {
+                    Ok(frame) => frames.push(frame.to_vec()),
+                    Err(e) if matches!(e.error, Expected::Incomplete(_)) => break,
+                    Err(e) => panic!("unexpected error: {}", e),
+                }
+
+                let cursor = input.get_ref();
+                cursor.start.len() - cursor.items.len()
+            };
+
+            buffer.drain(..consumed);
+        }
+    }
+
+    assert_eq!(frames, vec![b"hello world".to_vec(), b"foo".to_vec()]);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_partial_eof() {
+    let mut input = partial(b"");
+    assert!(matches!(eof(&mut input).unwrap_err().error, Expected::Incomplete(1)));
+}
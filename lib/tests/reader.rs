@@ -0,0 +1,118 @@
+use std::io::{self, Read};
+
+use pear::input::{Pear, ReaderInput, Extent, Rewind, Input as _};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<R> = Pear<ReaderInput<R>>;
+type Result<T, R> = pear::input::Result<T, ReaderInput<R>>;
+
+/// Yields its bytes one at a time, then fails with `error` if it is set.
+struct Trickle {
+    bytes: &'static [u8],
+    error: Option<io::ErrorKind>,
+}
+
+impl Read for Trickle {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.bytes.split_first() {
+            Some((&byte, rest)) if !buf.is_empty() => {
+                buf[0] = byte;
+                self.bytes = rest;
+                Ok(1)
+            }
+            Some(_) => Ok(0),
+            None => match self.error {
+                Some(kind) => Err(io::Error::new(kind, "connection reset")),
+                None => Ok(0),
+            }
+        }
+    }
+}
+
+fn trickle(bytes: &'static [u8]) -> Trickle {
+    Trickle { bytes, error: None }
+}
+
+#[parser]
+fn word<R: Read>(input: &mut Input<R>) -> Result<Vec<u8>, R> {
+    let word = take_some_while(|c| c.is_ascii_alphabetic())?;
+    skip_while(|&c| c == b' ')?;
+    word.values
+}
+
+#[parser]
+fn words<R: Read>(input: &mut Input<R>) -> Result<Vec<Vec<u8>>, R> {
+    eat_slice(b"words:")?;
+    skip_while(|&c| c == b' ')?;
+    let words: Vec<_> = collect(word)?;
+    words
+}
+
+#[test]
+fn test_reader_words() {
+    let words = parse!(words: ReaderInput::new(trickle(b"words: abc de f"))).unwrap();
+    assert_eq!(words, vec![b"abc".to_vec(), b"de".to_vec(), b"f".to_vec()]);
+}
+
+#[test]
+fn test_reader_slices() {
+    let mut input = Input::new(trickle(b"hello world"));
+    let slice = eat_slice(&mut input, b"hello").unwrap();
+    assert_eq!(slice, Extent { start: 0, end: 5, values: b"hello".to_vec() });
+    assert!(eat_slice(&mut input, b" planet").is_err());
+
+    let rest = take_while(&mut input, |_| true).unwrap();
+    assert_eq!(rest, Extent { start: 5, end: 11, values: b" world".to_vec() });
+}
+
+#[test]
+fn test_reader_rewind_in_window() {
+    let mut input = Input::new(ReaderInput::with_window(trickle(b"abcdefgh"), 4));
+    let mark = input.mark(&pear::input::ParserInfo::new("test", true));
+    take_n(&mut input, 3).unwrap();
+    input.rewind_to(mark);
+    assert_eq!(take_while(&mut input, |_| true).unwrap().values, b"abcdefgh");
+}
+
+#[parser]
+fn ends_in_x<R: Read>(input: &mut Input<R>) -> Result<(), R> {
+    skip_while(|&c| c == b'a')?;
+    eat(b'x')?;
+}
+
+#[test]
+fn test_reader_long_stream_stays_bounded() {
+    let bytes = vec![b'a'; 1024 * 1024];
+    let mut input = Input::new(ReaderInput::with_window(&bytes[..], 16));
+    assert!(ends_in_x(&mut input).is_err());
+    assert!(input.window_start() > bytes.len() - 16 * 1024);
+
+    let context = input.context(0);
+    assert_eq!((context.start, context.end), (0, bytes.len()));
+    assert_eq!(context.values.len(), 16);
+}
+
+#[test]
+#[should_panic(expected = "outside of retained window")]
+fn test_reader_rewind_past_window() {
+    let bytes = [b'a'; 64 * 1024];
+    let mut input = ReaderInput::with_window(&bytes[..], 16);
+    let mark = input.mark(&pear::input::ParserInfo::new("test", true));
+    input.skip(|_| true);
+    assert!(input.window_start() > 0);
+    input.rewind_to(mark);
+}
+
+#[test]
+fn test_reader_io_error() {
+    let reader = Trickle { bytes: b"words: abc", error: Some(io::ErrorKind::ConnectionReset) };
+    let error = parse!(words: ReaderInput::new(reader)).unwrap_err();
+    match error.error {
+        Expected::Io(ref e) => assert_eq!(e.kind(), io::ErrorKind::ConnectionReset),
+        ref other => panic!("expected I/O error, found {:?}", other),
+    }
+
+    let error = parse!(words: ReaderInput::new(trickle(b"words"))).unwrap_err();
+    assert!(matches!(error.error, Expected::Slice(_, None)));
+}
@@ -0,0 +1,132 @@
+use pear::input::{Pear, Text, Cursor, Partial};
+use pear::error::Expected;
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+type PartialResult<'a, T> = pear::input::Result<T, Partial<Cursor<&'a [u8]>>>;
+
+#[derive(Debug, Default, PartialEq)]
+struct Entry<'a> {
+    key: &'a str,
+    value: &'a str,
+}
+
+#[parser]
+fn entry<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, Entry<'a>> {
+    let key = take_some_while(|c| c.is_ascii_alphanumeric())?;
+    eat_slice(" = ")?;
+    let value = take_some_while(|&c| c != '\n')?;
+    Entry { key, value }
+}
+
+#[parser]
+fn config<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, Vec<Entry<'a>>> {
+    let mut entries = vec![];
+    while !succeeds(input, eof) {
+        entries.push(recover(entry, |&c| c == '\n')?);
+        if !succeeds(input, |i| eat(i, '\n')) {
+            break;
+        }
+    }
+
+    entries
+}
+
+#[parser]
+fn section<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, Vec<Entry<'a>>> {
+    eat_slice("[config]\n")?;
+    config()?
+}
+
+fn entry_at(key: &'static str, value: &'static str) -> Entry<'static> {
+    Entry { key, value }
+}
+
+#[test]
+fn test_recover_collects_diagnostics() {
+    let source = "a = 1\nb 2\nc = 3\n= 4\nd = 5";
+    let mut input = Pear::from(Text::from(source));
+    let entries = config(&mut input).unwrap();
+    assert_eq!(entries, vec![
+        entry_at("a", "1"),
+        Entry::default(),
+        entry_at("c", "3"),
+        Entry::default(),
+        entry_at("d", "5"),
+    ]);
+
+    let diagnostics = input.diagnostics();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].error.to_string(), "expected slice \" = \" but found \" 2\\n\"");
+    assert_eq!(diagnostics[0].info.context.end.0, 2);
+    assert_eq!(diagnostics[1].info.context.end.0, 4);
+}
+
+#[test]
+fn test_parse_recover() {
+    let (entries, diagnostics) = parse!(recover config: Text::from("a = 1\n!\nb = 2"));
+    assert_eq!(entries.unwrap().len(), 3);
+    assert_eq!(diagnostics.len(), 1);
+
+    let (entries, diagnostics) = parse!(recover config: Text::from("a = 1\nb = 2\n\n"));
+    assert_eq!(entries.unwrap().len(), 3);
+    assert_eq!(diagnostics.len(), 1);
+
+    // A failure outside of `recover` is fatal, but earlier diagnostics remain.
+    let (entries, diagnostics) = parse!(recover section: Text::from("[config]\n?"));
+    assert_eq!(entries.unwrap(), vec![Entry::default()]);
+    assert_eq!(diagnostics.len(), 1);
+
+    let (entries, diagnostics) = parse!(recover section: Text::from("[conf]\na = 1"));
+    assert!(entries.is_none());
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[parser]
+fn digit<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, char> {
+    let digit = eat_if(char::is_ascii_digit)?;
+    eat(';')?;
+    digit
+}
+
+#[parser]
+fn semicolon<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, char> {
+    eat(';')?
+}
+
+#[parser]
+fn lenient_a<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, char> {
+    recover(|i| eat(i, 'a'), |_| false)?
+}
+
+#[parser]
+fn abc<'a>(input: &mut Pear<Partial<Cursor<&'a [u8]>>>) -> PartialResult<'a, ()> {
+    eat_slice(&b"abc"[..])?;
+}
+
+#[test]
+fn test_recover_until_parser() {
+    let mut input = Pear::from(Text::from("1;;2;x y;3;"));
+    let mut digits = vec![];
+    while !succeeds(&mut input, eof) {
+        digits.push(recover_until(&mut input, digit, semicolon).unwrap());
+    }
+
+    assert_eq!(digits, ['1', '\0', '2', '\0', '3']);
+    assert_eq!(input.diagnostics().len(), 2);
+}
+
+#[test]
+fn test_recover_passes_through() {
+    // Errors that are being discarded aren't recorded.
+    let mut input = Pear::from(Text::from("x"));
+    assert!(ok(&mut input, lenient_a).is_none());
+    assert!(input.diagnostics().is_empty());
+    assert_eq!(eat_any(&mut input).unwrap(), 'x');
+
+    // Neither are errors from input that was cut short.
+    let mut input = Pear::from(Partial::new(Cursor::from(&b"ab"[..])));
+    let err = recover(&mut input, abc, |_| false).unwrap_err();
+    assert!(matches!(err.error, Expected::Incomplete(1)));
+    assert!(input.diagnostics().is_empty());
+}
@@ -0,0 +1,141 @@
+use pear::input::{Pear, Text, Bytes, SourceMap, ColumnUnit};
+use pear::error::Report;
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn number<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, &'a str> {
+    take_some_while(|c| c.is_ascii_digit())?
+}
+
+#[parser]
+fn list<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, Vec<&'a str>> {
+    eat('[')?;
+    let items: Vec<_> = series(number, ',')?;
+    eat(']')?;
+    items
+}
+
+#[parser]
+fn block<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, &'a str> {
+    eat_slice("begin\n")?;
+    let body = take_while(|&c| c != '!')?;
+    if !body.ends_with("end") {
+        parse_error!("unterminated block")?;
+    }
+
+    body
+}
+
+#[parser]
+fn word<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, &'a str> {
+    let word = take_while(|&c| c != ' ')?;
+    if word.starts_with('"') {
+        parse_error!("not a word")?;
+    }
+
+    word
+}
+
+fn report<'a>(err: &pear::input::ParseError<Text<'a>>, source: &str) -> String {
+    Report::new(err, source).color(false).to_string()
+}
+
+#[test]
+fn test_report_single_line() {
+    let source = "[1,2}";
+    let err = parse!(list: Text::from(source)).unwrap_err();
+    assert_eq!(report(&err, source), "\
+error: expected token ']' but found '}'
+ --> 1:5
+  |
+1 | [1,2}
+  |     ^
+  = note: in eat at 1:5
+  = note: in list at 1:1");
+}
+
+#[test]
+fn test_report_multi_line() {
+    let source = "begin\nfoo\n  bar\n!";
+    let err = parse!(block: Text::from(source)).unwrap_err();
+    assert_eq!(report(&err, source), "\
+error: unterminated block
+ --> 1:1
+  |
+1 | begin
+  | ^^^^^
+2 | foo
+  | ^^^
+3 |   bar
+  | ^^^^^
+  = note: in block at 1:1");
+}
+
+#[test]
+fn test_report_eof_and_tabs() {
+    let source = "\t[1,";
+    let mut input = Pear::new(Text::from(source));
+    eat(&mut input, '\t').unwrap();
+    let err = list(&mut input).unwrap_err();
+    assert_eq!(report(&err, source), "\
+error: unexpected EOF: expected some token
+ --> 1:5
+  |
+1 | \t[1,
+  | \t   ^
+  = note: in take_some_while at 1:5
+  = note: in number at 1:5
+  = note: in series at 1:3
+  = note: in list at 1:2");
+}
+
+#[test]
+fn test_report_gutter_width_and_source_map() {
+    let mut map = SourceMap::new();
+    map.add("empty.txt", "");
+    let id = map.add("list.txt", "\n\n\n\n\n\n\n\n\n[1,2 ]");
+
+    let mut input = Pear::new(map.text(id));
+    take_while(&mut input, |&c| c == '\n').unwrap();
+    let err = list(&mut input).unwrap_err();
+    let report = Report::in_map(&err, &map).color(false);
+    assert_eq!(report.to_string(), "\
+error: expected token ']' but found ' '
+  --> list.txt:10:5
+   |
+10 | [1,2 ]
+   |     ^
+   = note: in eat at list.txt:10:5
+   = note: in list at list.txt:10:1");
+}
+
+#[test]
+fn test_report_column_units() {
+    let source = "s = \"héllo\" + 1";
+    let error = |columns| {
+        let mut input = Pear::from(Text::from(source).with_columns(columns));
+        eat_slice(&mut input, "s = ").unwrap();
+        let err = word(&mut input).unwrap_err();
+        Report::new(&err, source).color(false).to_string()
+    };
+
+    assert!(error(ColumnUnit::Byte).ends_with("\n  |     ^^^^^^^^\n  = note: in word at 1:5"));
+    assert!(error(ColumnUnit::Char).ends_with("\n  |     ^^^^^^^\n  = note: in word at 1:5"));
+}
+
+#[test]
+fn test_report_byte_spans() {
+    let source = "[1,2}";
+    let mut input = Pear::from(Bytes::from(source));
+    eat(&mut input, b'[').unwrap();
+    let err = eat(&mut input, b']').unwrap_err();
+    assert_eq!(Report::new(&err, source).color(false).to_string(), "\
+error: expected token ']' but found '1'
+ --> 1:2
+  |
+1 | [1,2}
+  |  ^
+  = note: in eat at 1:2");
+}
@@ -0,0 +1,63 @@
+use pear::input::{Pear, Cursor, Bytes, Seek, Extent};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Pear<Cursor<&'a [u8]>>;
+type Result<'a, T> = pear::input::Result<T, Cursor<&'a [u8]>>;
+
+/// A length-prefixed string.
+#[parser]
+fn string<'a>(input: &mut Input<'a>) -> Result<'a, &'a [u8]> {
+    let len = eat_any()? as usize;
+    take_n_if(len, |_| true)?.values
+}
+
+/// A count, that many one-byte offsets to strings, then the strings' data.
+#[parser]
+fn table<'a>(input: &mut Input<'a>) -> Result<'a, Vec<&'a [u8]>> {
+    let count = eat_any()?;
+    let mut strings = vec![];
+    for _ in 0..count {
+        let offset = eat_any()? as usize;
+        strings.push(at(offset, string)?);
+    }
+
+    take_while(|_| true)?;
+    strings
+}
+
+#[test]
+fn test_seek_table() {
+    let data = b"\x02\x07\x03\x03abc\x02hi";
+    let strings = parse!(table: Cursor::from(&data[..])).unwrap();
+    assert_eq!(strings, vec![&b"hi"[..], &b"abc"[..]]);
+}
+
+#[test]
+fn test_seek_error_context() {
+    let data = b"\x01\x02\x05ab";
+    let err = parse!(table: Cursor::from(&data[..])).unwrap_err();
+    assert_eq!(err.info.context, Extent { start: 3, end: 5, values: &b"ab"[..] });
+
+    let frame = err.stack.iter().find(|f| f.parser.name == "string").unwrap();
+    assert_eq!((frame.context.start, frame.context.end), (2, 5));
+
+    let frame = err.stack.iter().find(|f| f.parser.name == "table").unwrap();
+    assert_eq!((frame.context.start, frame.context.end), (0, 2));
+}
+
+#[test]
+fn test_seek_out_of_bounds() {
+    let err = parse!(table: Cursor::from(&b"\x01\x09"[..])).unwrap_err();
+    assert_eq!(err.error.to_string(), "offset 9 is out of bounds");
+}
+
+#[test]
+fn test_seek_bytes() {
+    let mut input: Pear<Bytes<'_>> = Pear::new(b"abcdef");
+    assert!(input.seek_to(4));
+    assert_eq!(eat_any(&mut input).unwrap(), b'e');
+    assert!(input.seek_to(6));
+    assert!(eof(&mut input).is_ok());
+    assert!(!input.seek_to(7));
+    assert!(eof(&mut input).is_ok());
+}
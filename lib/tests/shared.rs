@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use pear::input::{Pear, Shared, SharedSlice, Seek};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input = Pear<Shared<str>>;
+type Result<T> = pear::input::Result<T, Shared<str>>;
+
+#[parser]
+fn word(input: &mut Input) -> Result<SharedSlice<str>> {
+    take_some_while(|c| c.is_ascii_alphabetic())?
+}
+
+#[parser]
+fn words(input: &mut Input) -> Result<Vec<SharedSlice<str>>> {
+    let words: Vec<_> = series(word, ' ')?;
+    eof()?;
+    words
+}
+
+#[test]
+fn test_shared_values_outlive_source() {
+    let words = {
+        let source = String::from("hello shared world");
+        parse!(words: Shared::from(source)).unwrap()
+    };
+
+    assert_eq!(words, vec!["hello", "shared", "world"]);
+    assert_eq!(words[1].range(), 6..12);
+    assert!(Arc::ptr_eq(words[0].source(), words[2].source()));
+}
+
+#[test]
+fn test_shared_error_is_send_and_static() {
+    fn check<T: Send + 'static>(_: &T) { }
+
+    let handle = std::thread::spawn(|| {
+        let source: Arc<str> = Arc::from("one two three 4");
+        parse!(words: Shared::from(source)).unwrap_err()
+    });
+
+    let err = handle.join().unwrap();
+    check(&err);
+    assert_eq!(err.info.context.end, (1, 15, 14));
+    assert_eq!(err.info.context.cursor, Some('4'));
+    assert!(err.to_string().contains("1:15"));
+}
+
+#[test]
+fn test_shared_eat_slice() {
+    let mut input = Pear::from(Shared::<str>::from("let x"));
+    assert_eq!(eat_slice(&mut input, "let").unwrap(), "let");
+    assert!(input.seek_to(4));
+    assert_eq!(eat_any(&mut input).unwrap(), 'x');
+    assert!(!input.seek_to(6));
+}
+
+#[test]
+fn test_shared_bytes() {
+    let mut input = Pear::from(Shared::from(vec![1u8, 2, 3, 4]));
+    assert_eq!(eat_any(&mut input).unwrap(), 1);
+    let rest = take_while(&mut input, |_| true).unwrap();
+    drop(input);
+    assert_eq!(rest, &[2, 3, 4]);
+}
@@ -0,0 +1,77 @@
+use pear::input::{Pear, Text, Span, SourceMap};
+use pear::{macros::*, parsers::*};
+
+type Result<'a, T> = pear::input::Result<T, Text<'a>>;
+
+#[parser]
+fn key_value<'a>(input: &mut Pear<Text<'a>>) -> Result<'a, (&'a str, Span<'a>)> {
+    let key = take_some_while(|c| c.is_ascii_alphanumeric())?;
+    let span = parse_context!();
+    eat_slice(" = ")?;
+    take_some_while(|&c| c != '\n')?;
+    eat('\n')?;
+    (key, span)
+}
+
+#[test]
+fn test_source_map_spans() {
+    let mut map = SourceMap::new();
+    let a = map.add("a.conf", "x = 1\n");
+    let b = map.add("b.conf", "x = 1\n");
+
+    let (key_a, span_a) = parse!(key_value: map.text(a)).unwrap();
+    let (key_b, span_b) = parse!(key_value: map.text(b)).unwrap();
+    assert_eq!(key_a, key_b);
+    assert_eq!(span_a.file.unwrap().id, a);
+    assert_eq!(span_b.file.unwrap().id, b);
+    assert_eq!(span_b.file.unwrap().name, "b.conf");
+
+    // Positions agree, but offsets never do.
+    assert_eq!((span_a.start.0, span_a.start.1), (span_b.start.0, span_b.start.1));
+    assert_ne!(span_a.start.2, span_b.start.2);
+    assert_eq!(map.file_at(span_a.start.2), Some(a));
+    assert_eq!(map.file_at(span_b.start.2), Some(b));
+    assert_eq!(map.file_at(span_b.end.2 + 100), None);
+}
+
+#[test]
+fn test_source_map_error_display() {
+    let mut map = SourceMap::new();
+    map.add("main.conf", "x = 1\n");
+    let id = map.add("conf/extra.conf", "y = 2\nz == 3\n");
+
+    let mut input = Pear::new(map.text(id));
+    key_value(&mut input).unwrap();
+    let error = key_value(&mut input).unwrap_err();
+    assert_eq!(error.info.context.start.0, 2);
+    assert_eq!(error.info.context.file.unwrap().id, id);
+
+    let string = error.to_string();
+    assert!(string.contains("(conf/extra.conf:2:2 "), "{}", string);
+    assert!(string.contains("+ key_value conf/extra.conf:2:1 to 2:2"), "{}", string);
+}
+
+#[test]
+fn test_source_map_rewind() {
+    let mut map = SourceMap::new();
+    map.add("a", "abc");
+    let id = map.add("b", "def");
+
+    let mut input: Pear<Text<'_>> = Pear::new(map.text(id));
+    let result = take_while_window(&mut input, 2, |&s| s != "ef").unwrap();
+    assert_eq!(result, "d");
+}
+
+#[test]
+fn test_source_map_positions() {
+    let mut map = SourceMap::new();
+    map.add("a.conf", "x = 1\n");
+    let b = map.add("b.conf", "y = 2\nz = 3\n");
+
+    let text = map.text(b);
+    let base = map.base(b);
+    assert_eq!(text.position(base), (1, 1));
+    assert_eq!(text.position(base + 8), (2, 3));
+    assert_eq!(text.position(0), (1, 1));
+    assert_eq!(text.position(base - 1), (1, 1));
+}
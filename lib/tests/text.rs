@@ -0,0 +1,50 @@
+use pear::input::Text;
+
+#[test]
+fn test_text_positions() {
+    let text = Text::from("ab\n\ncde\n");
+    let positions: Vec<_> = (0..=8).map(|offset| text.position(offset)).collect();
+    assert_eq!(positions, [
+        (1, 1), (1, 2), (1, 3),
+        (2, 1),
+        (3, 1), (3, 2), (3, 3), (3, 4),
+        (4, 1),
+    ]);
+}
+
+#[test]
+fn test_text_positions_many_lines() {
+    let source = "line\n".repeat(10_000);
+    let text = Text::from(source.as_str());
+    assert_eq!(text.position(0), (1, 1));
+    assert_eq!(text.position(5 * 4_321 + 3), (4_322, 4));
+    assert_eq!(text.position(source.len()), (10_001, 1));
+}
+
+#[test]
+fn test_text_positions_across_threads() {
+    let source = "a\nbb\nccc\n".repeat(100);
+    let text = Text::from(source.as_str());
+    std::thread::scope(|scope| {
+        for line in 0..3 {
+            let text = &text;
+            scope.spawn(move || {
+                let offset = [0, 2, 5][line] + 9 * 42;
+                assert_eq!(text.position(offset), (3 * 42 + line + 1, 1));
+            });
+        }
+    });
+}
+
+#[test]
+fn test_text_positions_clamped() {
+    let text = Text::from("aé\n😀");
+    assert_eq!(text.position(2), (1, 2));
+    assert_eq!(text.position(6), (2, 1));
+    assert_eq!(text.position(100), (2, 5));
+
+    let text = Text::from("aé\n😀").with_columns(pear::input::ColumnUnit::Char);
+    assert_eq!(text.position(2), (1, 2));
+    assert_eq!(text.position(6), (2, 1));
+    assert_eq!(text.position(100), (2, 2));
+}
@@ -0,0 +1,165 @@
+use std::ops::Range;
+
+use pear::input::{Pear, Tokens, Spanned, Span, Show, SourceMap};
+use pear::{macros::*, parsers::*, combinators::*};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind<'a> {
+    Ident(&'a str),
+    Num(u64),
+    Punct(char),
+}
+
+#[derive(Debug, Clone)]
+struct Token<'a> {
+    kind: Kind<'a>,
+    span: Range<usize>,
+}
+
+impl PartialEq for Token<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+    }
+}
+
+impl Spanned for Token<'_> {
+    fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl Show for Token<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            Kind::Ident(name) => write!(f, "identifier `{}`", name),
+            Kind::Num(n) => write!(f, "number {}", n),
+            Kind::Punct(c) => write!(f, "'{}'", c),
+        }
+    }
+}
+
+fn lex(source: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut chars = source.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let mut end = i + c.len_utf8();
+        let kind = if c.is_whitespace() {
+            continue
+        } else if c.is_alphanumeric() {
+            while let Some(&(j, c)) = chars.peek().filter(|(_, c)| c.is_alphanumeric()) {
+                end = j + c.len_utf8();
+                chars.next();
+            }
+
+            match source[i..end].parse() {
+                Ok(n) => Kind::Num(n),
+                Err(_) => Kind::Ident(&source[i..end]),
+            }
+        } else {
+            Kind::Punct(c)
+        };
+
+        tokens.push(Token { kind, span: i..end });
+    }
+
+    tokens
+}
+
+type Input<'a> = Pear<Tokens<'a, Token<'a>>>;
+type Result<'a, T> = pear::input::Result<T, Tokens<'a, Token<'a>>>;
+
+fn punct<'a>(c: char) -> Token<'a> {
+    Token { kind: Kind::Punct(c), span: 0..0 }
+}
+
+#[parser]
+fn ident<'a>(input: &mut Input<'a>) -> Result<'a, &'a str> {
+    match eat_any()?.kind {
+        Kind::Ident(name) => name,
+        _ => parse_error!("expected an identifier")?,
+    }
+}
+
+#[parser]
+fn num<'a>(input: &mut Input<'a>) -> Result<'a, u64> {
+    match eat_any()?.kind {
+        Kind::Num(n) => n,
+        _ => parse_error!("expected a number")?,
+    }
+}
+
+#[parser]
+fn sum<'a>(input: &mut Input<'a>) -> Result<'a, u64> {
+    let mut total = num()?;
+    while eat(input, punct('+')).is_ok() {
+        total += num()?;
+    }
+
+    total
+}
+
+#[parser]
+fn assignment<'a>(input: &mut Input<'a>) -> Result<'a, (&'a str, u64, Span<'a>)> {
+    let name = ident()?;
+    eat(punct('='))?;
+    let value = sum()?;
+    let span = parse_context!();
+    eat(punct(';'))?;
+    (name, value, span)
+}
+
+#[parser]
+fn assignments<'a>(input: &mut Input<'a>) -> Result<'a, Vec<(&'a str, u64, Span<'a>)>> {
+    let assignments: Vec<_> = collect(assignment)?;
+    assignments
+}
+
+#[test]
+fn test_tokens_spans() {
+    let source = "x = 1 + 2;\n  total = 30 + 4 + 5;";
+    let tokens = lex(source);
+    let result = parse!(assignments: Tokens::new(source, &tokens)).unwrap();
+    assert_eq!(result.len(), 2);
+
+    let (name, value, span) = result[1];
+    assert_eq!((name, value), ("total", 39));
+    assert_eq!(span.start, (2, 3, 13));
+    assert_eq!(span.end, (2, 21, 31));
+    assert_eq!(span.snippet, Some("total = 30 + 4 + 5"));
+    assert_eq!(span.cursor, Some(';'));
+}
+
+#[test]
+fn test_tokens_error_position() {
+    let source = "x = 1 +\n    + 2;";
+    let tokens = lex(source);
+    let err = parse!(assignment: Tokens::new(source, &tokens)).unwrap_err();
+    assert_eq!(err.info.context.start, (2, 5, 12));
+    assert_eq!(err.info.context.snippet, Some("+"));
+
+    let string = err.to_string();
+    assert!(string.contains("expected a number"), "{}", string);
+    assert!(string.contains("+ assignment 1:1 to 2:6 \"x = 1 +\\n    +"), "{}", string);
+}
+
+#[test]
+fn test_tokens_eof() {
+    let source = "x = 1 + 2 ";
+    let tokens = lex(source);
+    let err = parse!(assignment: Tokens::new(source, &tokens)).unwrap_err();
+    assert_eq!(err.info.context.start, (1, 10, 9));
+    assert_eq!(err.info.context.cursor, None);
+    assert!(err.to_string().contains("';'"), "{}", err);
+}
+
+#[test]
+fn test_tokens_source_map() {
+    let mut map = SourceMap::new();
+    map.add("a", "unused");
+    let id = map.add("b", "y = z;");
+    let tokens = lex(map.source(id));
+    let err = parse!(assignment: Tokens::new(map.text(id), &tokens)).unwrap_err();
+    assert_eq!(err.info.context.file.unwrap().id, id);
+    assert_eq!(err.info.context.start.2, map.base(id) + 4);
+    assert!(err.to_string().contains("b:1:5"), "{}", err);
+}
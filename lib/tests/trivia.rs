@@ -0,0 +1,86 @@
+use pear::input::{Pear, Text, Span, Trivia, Whitespace, Input as _};
+use pear::{macros::*, parsers::*, combinators::*};
+
+type Input<'a> = Pear<Trivia<Text<'a>, Whitespace>>;
+type Result<'a, T> = pear::input::Result<T, Trivia<Text<'a>, Whitespace>>;
+
+fn input(string: &str) -> Trivia<Text<'_>, Whitespace> {
+    let skip = Whitespace::new().line_comments("//").block_comments("/*", "*/");
+    Trivia::new(Text::from(string), skip)
+}
+
+#[parser]
+fn string<'a>(input: &mut Input<'a>) -> Result<'a, &'a str> {
+    eat('"')?;
+    let contents = raw(|i| take_while(i, |&c| c != '"'))?;
+    eat('"')?;
+    contents
+}
+
+#[parser]
+fn ident<'a>(input: &mut Input<'a>) -> Result<'a, &'a str> {
+    take_some_while(|c| c.is_ascii_alphanumeric())?
+}
+
+#[parser]
+fn entry<'a>(input: &mut Input<'a>) -> Result<'a, (&'a str, &'a str, Span<'a>)> {
+    let key = ident()?;
+    eat(':')?;
+    let value = string()?;
+    eat(';')?;
+    (key, value, parse_context!())
+}
+
+#[parser]
+fn entries<'a>(input: &mut Input<'a>) -> Result<'a, Vec<(&'a str, &'a str, Span<'a>)>> {
+    let entries: Vec<_> = collect(entry)?;
+    entries
+}
+
+#[test]
+fn test_trivia_skipped() {
+    let source = "
+        // The first entry.
+        a: \"x // y\";
+        b /* the key */ : /* the value */ \" /* z */ \" ;
+    ";
+
+    let entries = parse!(entries: input(source)).unwrap();
+    let values: Vec<_> = entries.iter().map(|e| (e.0, e.1)).collect();
+    assert_eq!(values, vec![("a", "x // y"), ("b", " /* z */ ")]);
+}
+
+#[test]
+fn test_trivia_context() {
+    let source = "  // comment\n  key: \"value\";  ";
+    let (_, _, span) = parse!(entry: input(source)).unwrap();
+    assert_eq!(span.start, (2, 3, 15));
+    assert_eq!(span.snippet, Some("key: \"value\";"));
+
+    let err = parse!(entry: input("\n\n   /* */ key \"value\";")).unwrap_err();
+    assert_eq!(err.info.context.start, (3, 14, 15));
+    assert_eq!(err.stack.last().unwrap().context.start, (3, 10, 11));
+}
+
+#[test]
+fn test_trivia_raw_mode() {
+    let mut input = Pear::from(input("  a b"));
+    assert!(raw(&mut input, ident).is_err());
+    assert!(!input.is_raw());
+    assert_eq!(eat(&mut input, 'a').unwrap(), 'a');
+
+    input.set_raw(true);
+    assert!(eat(&mut input, 'b').is_err());
+    input.set_raw(false);
+    assert_eq!(eat(&mut input, 'b').unwrap(), 'b');
+    assert!(eof(&mut input).is_ok());
+}
+
+#[test]
+fn test_trivia_closure() {
+    let text = Text::from("1 , 2 ,3");
+    let skip_spaces = |text: &mut Text<'_>| { text.skip(|&c| c == ' '); };
+    let mut input = Pear::from(Trivia::new(text, skip_spaces));
+    let digits: Vec<char> = series(&mut input, eat_any, ',').unwrap();
+    assert_eq!(digits, vec!['1', '2', '3']);
+}